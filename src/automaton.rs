@@ -0,0 +1,388 @@
+//! Cellular automata over a [`Grid`], for puzzles that update cells until nothing changes.
+//!
+//! A rule gets a [`Cell`] with its state and its neighbours and returns the next state. Only
+//! cells whose neighbourhood changed in the previous step are evaluated again, so a simulation
+//! that settles down gets cheaper with every step.
+//!
+//! ```
+//! use advent_of_code::{
+//!     Grid,
+//!     automaton::{Automaton, Neighbourhood, Outcome},
+//! };
+//!
+//! // fire spreads to every tree next to it.
+//! let grid = Grid::parse("*TT.T\nT...T", Some);
+//! let mut fire = Automaton::new(grid, Neighbourhood::Orthogonal, |cell| match cell.state {
+//!     'T' if cell.count(|n| *n == '*') > 0 => '*',
+//!     state => *state,
+//! });
+//! assert_eq!(fire.run(100), Outcome::Fixpoint { steps: 2 });
+//! assert_eq!(fire.grid().locations.values().filter(|c| **c == 'T').count(), 2);
+//! ```
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+};
+
+use itertools::Itertools;
+
+use crate::{Grid, Location};
+
+/// Which cells around a cell are its neighbours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The four cells sharing an edge.
+    Orthogonal,
+    /// The eight cells sharing an edge or a corner.
+    Moore,
+}
+
+impl Neighbourhood {
+    fn offsets(&self) -> &'static [(i64, i64)] {
+        match self {
+            Neighbourhood::Orthogonal => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+            Neighbourhood::Moore => &[
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+        }
+    }
+}
+
+/// When new states become visible to the rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Update {
+    /// Every cell sees the states from before the step.
+    Synchronous,
+    /// Cells are updated one by one in reading order, and later cells see the new states of
+    /// earlier ones within the same step.
+    InPlace,
+}
+
+/// How a [`Automaton::run`] ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Nothing changes anymore, after this many steps that did change something.
+    Fixpoint { steps: usize },
+    /// The state after step `start + length` is the same as after step `start`.
+    Cycle { start: usize, length: usize },
+    /// The iteration cap was reached first.
+    LimitReached,
+}
+
+/// A cell as seen by a rule.
+pub struct Cell<'a, T> {
+    pub location: Location,
+    pub state: &'a T,
+    neighbours: Vec<(Location, &'a T)>,
+}
+
+impl<'a, T> Cell<'a, T> {
+    fn new(grid: &'a Grid<T>, neighbourhood: Neighbourhood, location: Location) -> Self {
+        let neighbours = neighbourhood
+            .offsets()
+            .iter()
+            .filter_map(|o| offset(&location, *o))
+            .filter_map(|l| grid.locations.get(&l).map(|state| (l, state)))
+            .collect();
+        Self {
+            location,
+            state: &grid.locations[&location],
+            neighbours,
+        }
+    }
+
+    /// The state of the neighbour at the given offset, if it is in the grid and the
+    /// neighbourhood.
+    pub fn neighbour(&self, dx: i64, dy: i64) -> Option<&'a T> {
+        let location = offset(&self.location, (dx, dy))?;
+        self.neighbours
+            .iter()
+            .find(|(l, _)| *l == location)
+            .map(|(_, state)| *state)
+    }
+
+    /// The neighbours that are in the grid.
+    pub fn neighbours(&self) -> impl Iterator<Item = (Location, &'a T)> + '_ {
+        self.neighbours.iter().copied()
+    }
+
+    /// The number of neighbours whose state matches.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.neighbours.iter().filter(|(_, s)| predicate(s)).count()
+    }
+}
+
+fn offset(location: &Location, (dx, dy): (i64, i64)) -> Option<Location> {
+    Some(Location {
+        x: location.x.checked_add_signed(dx.try_into().ok()?)?,
+        y: location.y.checked_add_signed(dy.try_into().ok()?)?,
+    })
+}
+
+fn fingerprint<T: Hash>(location: &Location, state: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (location, state).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The grids after earlier steps, with the number of the step.
+type Snapshots<T> = Vec<(usize, HashMap<Location, T>)>;
+
+/// A grid whose cells are updated by a rule.
+pub struct Automaton<T, F> {
+    grid: Grid<T>,
+    neighbourhood: Neighbourhood,
+    update: Update,
+    rule: F,
+    /// The cells to evaluate in the next step.
+    worklist: HashSet<Location>,
+    /// An order independent hash of the whole grid, kept up to date on every change.
+    fingerprint: u64,
+    steps: usize,
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&Cell<T>) -> T,
+{
+    /// A synchronously updated automaton.
+    pub fn new(grid: Grid<T>, neighbourhood: Neighbourhood, rule: F) -> Self {
+        let fingerprint = grid
+            .locations
+            .iter()
+            .fold(0, |sum: u64, (l, s)| sum.wrapping_add(fingerprint(l, s)));
+        Self {
+            worklist: grid.locations.keys().copied().collect(),
+            grid,
+            neighbourhood,
+            update: Update::Synchronous,
+            rule,
+            fingerprint,
+            steps: 0,
+        }
+    }
+
+    pub fn with_update(mut self, update: Update) -> Self {
+        self.update = update;
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// The number of steps taken so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Changes the state of a cell and returns the cells whose neighbourhood changed with it.
+    fn set(&mut self, location: Location, state: T) -> Vec<Location> {
+        let old = self.grid.locations.insert(location, state).unwrap();
+        self.fingerprint = self
+            .fingerprint
+            .wrapping_sub(fingerprint(&location, &old))
+            .wrapping_add(fingerprint(&location, &self.grid.locations[&location]));
+
+        self.neighbourhood
+            .offsets()
+            .iter()
+            .filter_map(|o| offset(&location, (-o.0, -o.1)))
+            .filter(|l| self.grid.locations.contains_key(l))
+            .chain([location])
+            .collect()
+    }
+
+    /// Applies the rule once and returns the number of cells that changed.
+    pub fn step(&mut self) -> usize {
+        let worklist = std::mem::take(&mut self.worklist);
+        let mut next = HashSet::new();
+        let mut changed = 0;
+
+        match self.update {
+            Update::Synchronous => {
+                let changes = worklist
+                    .into_iter()
+                    .sorted_by_key(|l| (l.y, l.x))
+                    .filter_map(|location| {
+                        let cell = Cell::new(&self.grid, self.neighbourhood, location);
+                        let state = (self.rule)(&cell);
+                        (state != *cell.state).then_some((location, state))
+                    })
+                    .collect_vec();
+                for (location, state) in changes {
+                    next.extend(self.set(location, state));
+                    changed += 1;
+                }
+            }
+            Update::InPlace => {
+                // the cells still to visit in this sweep, in reading order.
+                let mut pass: BTreeSet<(u32, u32)> =
+                    worklist.into_iter().map(|l| (l.y, l.x)).collect();
+
+                while let Some((y, x)) = pass.pop_first() {
+                    let location = Location { x, y };
+                    let cell = Cell::new(&self.grid, self.neighbourhood, location);
+                    let state = (self.rule)(&cell);
+                    if state == *cell.state {
+                        continue;
+                    }
+                    changed += 1;
+                    for dirty in self.set(location, state) {
+                        // cells later in reading order still see the change in this sweep.
+                        if (dirty.y, dirty.x) > (y, x) {
+                            pass.insert((dirty.y, dirty.x));
+                        } else {
+                            next.insert(dirty);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.worklist = next;
+        self.steps += 1;
+        changed
+    }
+
+    /// Steps until nothing changes, the grid repeats an earlier state, or `max_steps` steps were
+    /// taken. States are compared by a hash of the grid first, and by the grid itself when the
+    /// hash repeats.
+    pub fn run(&mut self, max_steps: usize) -> Outcome {
+        let mut seen: HashMap<u64, Snapshots<T>> = HashMap::new();
+        seen.insert(
+            self.fingerprint,
+            vec![(self.steps, self.grid.locations.clone())],
+        );
+
+        for _ in 0..max_steps {
+            if self.step() == 0 {
+                return Outcome::Fixpoint {
+                    steps: self.steps - 1,
+                };
+            }
+
+            let snapshots = seen.entry(self.fingerprint).or_default();
+            if let Some((start, _)) = snapshots
+                .iter()
+                .find(|(_, grid)| *grid == self.grid.locations)
+            {
+                return Outcome::Cycle {
+                    start: *start,
+                    length: self.steps - start,
+                };
+            }
+            snapshots.push((self.steps, self.grid.locations.clone()));
+        }
+        Outcome::LimitReached
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Automaton, Neighbourhood, Outcome, Update};
+    use crate::Grid;
+
+    #[cfg(test)]
+    fn life(input: &str) -> Automaton<char, impl FnMut(&super::Cell<char>) -> char> {
+        let grid = Grid::parse(input, Some);
+        Automaton::new(grid, Neighbourhood::Moore, |cell| {
+            match (cell.state, cell.count(|n| *n == '#')) {
+                ('#', 2 | 3) | ('.', 3) => '#',
+                _ => '.',
+            }
+        })
+    }
+
+    #[test]
+    fn detects_fixpoints() {
+        let mut block = life("....\n.##.\n.##.\n....");
+        assert_eq!(block.run(10), Outcome::Fixpoint { steps: 0 });
+        assert_eq!(block.steps(), 1);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let mut blinker = life(".....\n..#..\n..#..\n..#..\n.....");
+        assert_eq!(
+            blinker.run(10),
+            Outcome::Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+        assert_eq!(blinker.grid().get(1, 2), Some(&'.'));
+
+        let mut glider = life(".#......\n..#.....\n###.....\n........\n........\n........");
+        assert_eq!(glider.run(3), Outcome::LimitReached);
+    }
+
+    #[test]
+    fn only_evaluates_changed_neighbourhoods() {
+        let mut evaluations = 0;
+        let grid = Grid::parse("#.........", Some);
+        let mut spread = Automaton::new(grid, Neighbourhood::Orthogonal, |cell| {
+            evaluations += 1;
+            match cell.neighbour(-1, 0) {
+                Some('#') => '#',
+                _ => *cell.state,
+            }
+        });
+        assert_eq!(spread.run(100), Outcome::Fixpoint { steps: 9 });
+        assert_eq!(spread.steps(), 10);
+        drop(spread);
+        // all ten cells at first, then at most the changed cell and its two neighbours.
+        assert!(evaluations <= 10 + 9 * 3);
+    }
+
+    #[test]
+    fn sweeps_changes_through_later_cells() {
+        // counts down, then spreads to the right.
+        let grid = Grid::parse("3..", Some);
+        let mut fuse = Automaton::new(grid, Neighbourhood::Orthogonal, |cell| {
+            match (cell.state, cell.neighbour(-1, 0)) {
+                ('1', _) => '#',
+                (digit @ '2'..='9', _) => (*digit as u8 - 1) as char,
+                ('.', Some('#')) => '#',
+                (state, _) => *state,
+            }
+        })
+        .with_update(Update::InPlace);
+
+        // only the fuse itself and its right neighbour are on the worklist after two steps.
+        assert_eq!(fuse.step(), 1);
+        assert_eq!(fuse.step(), 1);
+        assert_eq!(fuse.step(), 3);
+        assert_eq!(fuse.grid().get(2, 0), Some(&'#'));
+        assert_eq!(fuse.run(10), Outcome::Fixpoint { steps: 3 });
+    }
+
+    #[test]
+    fn updates_in_place() {
+        let grid = Grid::parse("#.........", Some);
+        let mut spread = Automaton::new(grid, Neighbourhood::Orthogonal, |cell| {
+            match cell.neighbour(-1, 0) {
+                Some('#') => '#',
+                _ => *cell.state,
+            }
+        })
+        .with_update(Update::InPlace);
+        // the change sweeps through the whole row in reading order in one step.
+        assert_eq!(spread.step(), 9);
+        assert_eq!(spread.run(100), Outcome::Fixpoint { steps: 1 });
+    }
+}
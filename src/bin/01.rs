@@ -1,25 +1,24 @@
 advent_of_code::solution!(1);
 
-fn parse_input(input: &str) -> Vec<(char, i32)> {
-    input
-        .lines()
-        .map(|line| {
-            (
-                line.chars().next().unwrap(),
-                line[1..].parse::<i32>().unwrap(),
-            )
-        })
-        .collect()
+advent_of_code::line_parser! {
+    struct Rotation = "{direction}{amount}" {
+        direction: char,
+        amount: i32,
+    }
+}
+
+fn parse_input(input: &str) -> Vec<Rotation> {
+    Rotation::parse_lines(input).unwrap_or_else(|e| panic!("invalid input: {e}"))
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut dial: i32 = 50;
     let mut count: u64 = 0;
     let inputs = parse_input(input);
-    for (direction, amount) in inputs {
-        dial = match direction {
-            'L' => (dial - amount).rem_euclid(100),
-            'R' => (dial + amount).rem_euclid(100),
+    for rotation in inputs {
+        dial = match rotation.direction {
+            'L' => (dial - rotation.amount).rem_euclid(100),
+            'R' => (dial + rotation.amount).rem_euclid(100),
             _ => panic!("unknown direction"),
         };
         if dial == 0 {
@@ -33,9 +32,9 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut dial: i32 = 50;
     let mut count: u64 = 0;
     let inputs = parse_input(input);
-    for (direction, amount) in inputs {
-        for _ in 0..amount {
-            dial = match direction {
+    for rotation in inputs {
+        for _ in 0..rotation.amount {
+            dial = match rotation.direction {
                 'L' => (dial - 1).rem_euclid(100),
                 'R' => (dial + 1).rem_euclid(100),
                 _ => panic!("unknown direction"),
@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use advent_of_code::math::number::{digit_count, divisors, repeat_multiplier};
 
 advent_of_code::solution!(2);
 
@@ -15,54 +15,74 @@ fn parse_input(input: &str) -> Vec<(u64, u64)> {
         .collect()
 }
 
-fn is_invalid_part_one(input: u64) -> bool {
-    let input_string = input.to_string();
-    if input_string.len().rem_euclid(2) == 1 {
-        return false;
+/// The sum of the numbers in `low..=high` that are a block of `width` digits, without a leading
+/// zero, repeated `times` times.
+fn sum_repeated(low: u64, high: u64, width: u32, times: u32) -> u128 {
+    // a multiplier that does not fit in a `u64` is larger than any number in the range.
+    let Some(multiplier) = repeat_multiplier(width, times) else {
+        return 0;
+    };
+    let first = low.div_ceil(multiplier).max(10_u64.pow(width - 1));
+    let last = (high / multiplier).min(10_u64.checked_pow(width).map_or(u64::MAX, |n| n - 1));
+    if first > last {
+        return 0;
     }
-    let (left, right) = input_string.split_at(input_string.len() / 2);
-    left == right
+    // the blocks form an arithmetic series.
+    let blocks = (u128::from(first) + u128::from(last)) * u128::from(last - first + 1) / 2;
+    blocks * u128::from(multiplier)
+}
+
+/// The sum of the invalid IDs in `low..=high`: a block of digits repeated exactly twice.
+fn sum_invalid_part_one(low: u64, high: u64) -> u128 {
+    (digit_count(low)..=digit_count(high))
+        .filter(|digits| digits % 2 == 0)
+        .map(|digits| sum_repeated(low, high, digits / 2, 2))
+        .sum()
+}
+
+/// The sum of the invalid IDs in `low..=high`: a block of digits repeated at least twice.
+fn sum_invalid_part_two(low: u64, high: u64) -> u128 {
+    (digit_count(low)..=digit_count(high))
+        .map(|digits| {
+            // the sum of the numbers by the width of their shortest repeated block, as a number
+            // made of blocks of width `w` is also made of blocks of every multiple of `w`.
+            let mut shortest: Vec<(u64, u128)> = vec![];
+            for width in divisors(u64::from(digits)) {
+                let repeated = sum_repeated(low, high, width as u32, digits / width as u32);
+                let shorter: u128 = shortest
+                    .iter()
+                    .filter(|(w, _)| width.is_multiple_of(*w))
+                    .map(|(_, sum)| sum)
+                    .sum();
+                shortest.push((width, repeated - shorter));
+            }
+            shortest
+                .iter()
+                .filter(|(width, _)| *width < u64::from(digits))
+                .map(|(_, sum)| sum)
+                .sum::<u128>()
+        })
+        .sum()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     let input = parse_input(input);
-
     let result = input
         .iter()
-        .flat_map(|(left, right)| (*left..=*right).collect_vec())
-        .filter(|input: &u64| is_invalid_part_one(*input))
-        .sum();
-
-    Some(result)
-}
+        .map(|(left, right)| sum_invalid_part_one(*left, *right))
+        .sum::<u128>();
 
-fn is_invalid_part_two(input: u64) -> bool {
-    let input_string = input.to_string();
-    for i in 2..=input_string.len() {
-        if input_string.len().rem_euclid(i) != 0 {
-            continue;
-        }
-        let chunks = input_string
-            .as_bytes()
-            .chunks(input_string.len() / i)
-            .collect_vec();
-        if chunks.iter().all_equal() {
-            return true;
-        }
-    }
-    false
+    Some(u64::try_from(result).expect("the sum of the invalid IDs does not fit in a u64"))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let input = parse_input(input);
-
     let result = input
         .iter()
-        .flat_map(|(left, right)| (*left..=*right).collect::<Vec<u64>>())
-        .filter(|input: &u64| is_invalid_part_two(*input))
-        .sum();
+        .map(|(left, right)| sum_invalid_part_two(*left, *right))
+        .sum::<u128>();
 
-    Some(result)
+    Some(u64::try_from(result).expect("the sum of the invalid IDs does not fit in a u64"))
 }
 
 #[cfg(test)]
@@ -71,11 +91,34 @@ mod tests {
 
     #[test]
     fn test_invalid_id() {
-        assert!(is_invalid_part_one(446446));
-        assert!(!is_invalid_part_one(145673));
-        assert!(is_invalid_part_two(446446));
-        assert!(is_invalid_part_two(565656));
-        assert!(is_invalid_part_two(824824824));
+        assert_eq!(sum_invalid_part_one(446446, 446446), 446446);
+        assert_eq!(sum_invalid_part_one(145673, 145673), 0);
+        assert_eq!(sum_invalid_part_two(446446, 446446), 446446);
+        assert_eq!(sum_invalid_part_two(565656, 565656), 565656);
+        assert_eq!(sum_invalid_part_two(824824824, 824824824), 824824824);
+    }
+
+    #[test]
+    fn test_invalid_ids_across_digit_counts() {
+        // 99, 111, 222, ..., 999 and 1010.
+        assert_eq!(sum_invalid_part_two(95, 1010), 99 + 111 * 45 + 1010);
+        assert_eq!(sum_invalid_part_one(95, 1010), 99 + 1010);
+    }
+
+    #[test]
+    fn test_invalid_ids_near_the_largest_id() {
+        // the only 20 digit ID made of a repeated block that is not larger than `u64::MAX`.
+        let largest = 18446744071844674407;
+        assert_eq!(sum_invalid_part_one(largest, u64::MAX), u128::from(largest));
+        assert_eq!(sum_invalid_part_two(largest, u64::MAX), u128::from(largest));
+        assert_eq!(sum_invalid_part_two(largest + 1, u64::MAX), 0);
+
+        // the blocks 1000000000..=1844674407, each repeated twice, add up to more than `u64::MAX`.
+        let blocks = (1000000000 + 1844674407) * 844674408 / 2;
+        assert_eq!(
+            sum_invalid_part_one(10_u64.pow(19), u64::MAX),
+            blocks * 10000000001
+        );
     }
 
     #[test]
@@ -1,5 +1,6 @@
 use std::cmp::max as max_cmp;
 
+use advent_of_code::math::number::{concat, from_digits};
 use itertools::Itertools;
 
 advent_of_code::solution!(3);
@@ -9,7 +10,7 @@ fn parse(input: &str) -> Vec<Vec<u8>> {
         .lines()
         .map(|line| {
             line.chars()
-                .map(|char| char.to_string().parse::<u8>().unwrap())
+                .map(|char| char.to_digit(10).unwrap() as u8)
                 .collect()
         })
         .collect_vec()
@@ -23,7 +24,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         for (index, num) in input.iter().enumerate() {
             let remaining_nums = &input[(index + 1)..];
             for remaining_num in remaining_nums {
-                best = max_cmp(best, (num * 10 + remaining_num) as u64);
+                best = max_cmp(best, concat(u64::from(*num), u64::from(*remaining_num)));
             }
         }
         result += best;
@@ -36,15 +37,15 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut result = 0;
     for input in inputs {
         let mut index = 0;
-        let mut inner_result = String::from("");
+        let mut digits = vec![];
         for num in (0..12).rev() {
             let options = &input[index..(input.len() - num)];
             let best_option = options.iter().max().unwrap();
             let (best_index, _) = options.iter().find_position(|x| *x == best_option).unwrap();
-            inner_result.push_str(&best_option.to_string());
+            digits.push(*best_option);
             index = index + best_index + 1;
         }
-        result += inner_result.parse::<u64>().unwrap()
+        result += from_digits(digits)
     }
 
     Some(result)
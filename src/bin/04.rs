@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use advent_of_code::{Grid, Location};
+use advent_of_code::{
+    Grid, Location,
+    automaton::{Automaton, Neighbourhood, Outcome},
+};
 use itertools::Itertools;
 
 advent_of_code::solution!(4);
@@ -45,27 +48,19 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid = parse(input);
-    let mut count = 0;
-
-    loop {
-        let accessible_papers = find_accessible_papers(&grid)
-            .into_iter()
-            .map(|(l, _)| *l)
-            .collect_vec();
-
-        if accessible_papers.is_empty() {
-            break;
-        }
-
-        count += accessible_papers.len();
+    let grid = parse(input);
+    let count_papers = |grid: &Grid<char>| grid.locations.values().filter(|c| **c == '@').count();
+    let papers = count_papers(&grid);
 
-        for accessible_paper in accessible_papers {
-            grid.locations.insert(accessible_paper, 'x');
-        }
-    }
+    // keep removing accessible papers until none are left.
+    let mut removal = Automaton::new(grid, Neighbourhood::Moore, |cell| match cell.state {
+        '@' if cell.count(|c| *c == '@') < 4 => 'x',
+        state => *state,
+    });
+    let outcome = removal.run(usize::MAX);
+    assert!(matches!(outcome, Outcome::Fixpoint { .. }));
 
-    Some(count.try_into().unwrap())
+    Some((papers - count_papers(removal.grid())).try_into().unwrap())
 }
 
 #[cfg(test)]
@@ -1,66 +1,33 @@
-use itertools::{Itertools, any};
-use std::ops::RangeInclusive;
+use advent_of_code::{interval::IntervalSet, template::input::sections};
+use itertools::Itertools;
 
 advent_of_code::solution!(5);
 
-fn parse(input: &str) -> (Vec<RangeInclusive<u64>>, Vec<u64>) {
-    let mut lines = input.lines();
-    let ranges = lines
-        .by_ref()
-        .take_while(|l| !l.is_empty())
+fn parse(input: &str) -> (IntervalSet<u64>, Vec<u64>) {
+    let sections = sections(input);
+    let (ranges, ids) = (sections[0], sections.get(1).copied().unwrap_or_default());
+
+    let ranges = ranges
+        .lines()
         .map(|l| {
             let (left, right) = l.split_once("-").unwrap();
-            RangeInclusive::new(left.parse().unwrap(), right.parse().unwrap())
+            left.parse().unwrap()..=right.parse().unwrap()
         })
-        .collect_vec();
+        .collect();
 
-    let ids = lines.map(|l| l.parse().unwrap()).collect_vec();
+    let ids = ids.lines().map(|l| l.parse().unwrap()).collect_vec();
 
     (ranges, ids)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     let (ranges, ids) = parse(input);
-    Some(
-        ids.iter()
-            .filter(|id| any(ranges.clone(), |range| range.contains(id)))
-            .count()
-            .try_into()
-            .unwrap(),
-    )
+    Some(ids.iter().filter(|id| ranges.contains(**id)).count() as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let (ranges, _) = parse(input);
-    let sorted_ranges = ranges
-        .into_iter()
-        .sorted_by_key(|r| (*r.start(), *r.end()))
-        .collect_vec();
-
-    let merged_ranges: Vec<RangeInclusive<u64>> = sorted_ranges
-        .into_iter()
-        .coalesce(|left, right| {
-            // fully contained
-            if left.contains(right.start()) && left.contains(right.end()) {
-                Ok(RangeInclusive::new(*left.start(), *left.end()))
-            }
-            // partly overlapping
-            else if left.contains(right.start()) || left.contains(right.end()) {
-                Ok(RangeInclusive::new(*left.start(), *right.end()))
-            }
-            // no overlap
-            else {
-                Err((left, right))
-            }
-        })
-        .collect_vec();
-
-    Some(
-        merged_ranges
-            .into_iter()
-            .map(|range| std::convert::TryInto::<u64>::try_into(range.count()).unwrap())
-            .sum(),
-    )
+    Some(ranges.len().try_into().unwrap())
 }
 
 #[cfg(test)]
@@ -1,43 +1,28 @@
 advent_of_code::solution!(6);
 
+use advent_of_code::template::input::{body_footer, column_blocks};
 use itertools::Itertools;
 
-// https://stackoverflow.com/questions/64498617/how-to-transpose-a-vector-of-vectors-in-rust
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>>
-where
-    T: Clone,
-{
-    (0..v[0].len())
-        .map(|i| v.iter().map(|inner| inner[i].clone()).collect::<Vec<T>>())
-        .collect()
-}
-
-fn parse_part_one(input: &str) -> Vec<(char, Vec<u64>)> {
-    let mut lines = input.lines();
-
-    let result: Vec<Vec<u64>> = transpose(
-        lines
-            .take_while_ref(|line| !line.contains("*") && !line.contains("+"))
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|num| num.parse().unwrap())
-                    .collect()
-            })
-            .collect_vec(),
-    );
-
-    let operations = lines
-        .last()
-        .unwrap()
-        .chars()
-        .filter(|c| *c == '*' || *c == '+')
-        .collect_vec();
-
-    operations.into_iter().zip_eq(result).collect_vec()
+/// Parses the blocks of numbers with the operator below each, reading numbers by row or by column.
+fn parse(input: &str, by_column: bool) -> Vec<(char, Vec<u64>)> {
+    let (numbers, operators) = body_footer(input).unwrap();
+
+    column_blocks(numbers)
+        .into_iter()
+        .map(|block| {
+            let operator = block.slice(operators).trim().chars().next().unwrap();
+            let numbers = if by_column {
+                block.column_numbers().unwrap()
+            } else {
+                block.row_numbers().unwrap()
+            };
+            (operator, numbers)
+        })
+        .collect_vec()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let inputs = parse_part_one(input);
+    let inputs = parse(input, false);
     Some(
         inputs
             .into_iter()
@@ -55,45 +40,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     )
 }
 
-// TODO: this could be written a bit neater
-fn parse_part_two(input: &str) -> Vec<(char, Vec<u64>)> {
-    // transpose all numbers
-    let inputs = transpose(
-        input
-            .lines()
-            .take_while_ref(|line| !line.contains("*") && !line.contains("+"))
-            .map(|line| line.chars().collect_vec())
-            .collect_vec(),
-    );
-
-    // split based on empty row (the divider between groups), then convert to number
-    let numbers: Vec<Vec<u64>> = inputs
-        .iter()
-        .map(|number| number.iter().collect::<String>())
-        .collect_vec()
-        .split(|number| number.trim().is_empty())
-        .map(|s| s.to_vec())
-        .map(|numbers| {
-            numbers
-                .iter()
-                .map(|number| number.trim().parse().unwrap())
-                .collect_vec()
-        })
-        .collect_vec();
-
-    let operations = input
-        .lines()
-        .last()
-        .unwrap()
-        .chars()
-        .filter(|c| *c == '*' || *c == '+')
-        .collect_vec();
-
-    operations.into_iter().zip_eq(numbers).collect_vec()
-}
-
 pub fn part_two(input: &str) -> Option<u64> {
-    let inputs = parse_part_two(input);
+    let inputs = parse(input, true);
     Some(
         inputs
             .into_iter()
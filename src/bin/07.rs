@@ -1,8 +1,11 @@
 use core::panic;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use advent_of_code::{Direction, Grid, Location};
-use itertools::Itertools;
+use advent_of_code::{
+    Direction, Grid, Location,
+    automaton::{Automaton, Neighbourhood, Outcome},
+    memo::Memo,
+};
 
 advent_of_code::solution!(7);
 
@@ -26,105 +29,53 @@ fn parse(input: &str) -> Grid<char> {
     }
 }
 
-fn get_beams(grid: &Grid<char>) -> Vec<(Location, char)> {
-    grid.locations
-        .iter()
-        .filter(|(_, c)| **c == '|')
-        .map(|(l, c)| (*l, *c))
-        .collect_vec()
-}
-
-fn count_beams(grid: &Grid<char>) -> usize {
-    grid.locations.iter().filter(|(_, c)| **c == '|').count()
-}
-
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut grid = parse(input);
-    let mut splits: HashSet<Location> = HashSet::new();
-
-    loop {
-        let beams_before = count_beams(&grid);
-        let beams = get_beams(&grid);
-
-        for (l, _) in beams {
-            if let Some((down, c)) = grid.get_by_direction(&l, Direction::Down) {
-                match c {
-                    '.' => grid.locations.insert(down, '|'),
-                    '^' => {
-                        splits.insert(down);
-                        if let Some((left, _)) = grid.get_by_direction(&down, Direction::Left) {
-                            grid.locations.insert(left, '|');
-                        };
-                        if let Some((right, _)) = grid.get_by_direction(&down, Direction::Right) {
-                            grid.locations.insert(right, '|');
-                        };
-                        None
-                    }
-                    '|' => None,
-                    _ => panic!("unknown"),
-                };
-            }
-        }
-
-        let beams_after = count_beams(&grid);
-        if beams_before == beams_after {
-            break;
+    let grid = parse(input);
+
+    // beams move down, and a splitter sends a beam coming from above to its left and right.
+    let mut beams = Automaton::new(grid, Neighbourhood::Moore, |cell| match cell.state {
+        '.' if cell.neighbour(0, -1) == Some(&'|') => '|',
+        '.' if [-1, 1].into_iter().any(|dx| {
+            cell.neighbour(dx, 0) == Some(&'^') && cell.neighbour(dx, -1) == Some(&'|')
+        }) =>
+        {
+            '|'
         }
-    }
-    Some(splits.len().try_into().unwrap())
-}
-
-fn update_count(beams: &mut HashMap<Location, u64>, location: Location, count: u64) {
-    beams
-        .entry(location)
-        .and_modify(|counter| *counter += count)
-        .or_insert(count);
+        state => *state,
+    });
+    let outcome = beams.run(usize::MAX);
+    assert!(matches!(outcome, Outcome::Fixpoint { .. }));
+
+    let grid = beams.grid();
+    let splits = grid
+        .locations
+        .iter()
+        .filter(|(l, c)| **c == '^' && l.y > 0 && grid.get(l.x, l.y - 1) == Some(&'|'))
+        .count();
+    Some(splits.try_into().unwrap())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid = parse(input);
-    let mut options: u64 = 0;
-    let mut beams: HashMap<Location, u64> = HashMap::new();
-    beams.insert(
-        *grid.locations.iter().find(|(_, c)| **c == '|').unwrap().0,
-        1,
-    );
-
-    loop {
-        let mut next_beams: HashMap<Location, u64> = HashMap::new();
-
-        for (beam, count) in beams.clone() {
-            if let Some((down, c)) = grid.get_by_direction(&beam, Direction::Down) {
-                match c {
-                    '.' => update_count(&mut next_beams, down, count),
-                    '^' => {
-                        if let Some((left, _)) = grid.get_by_direction(&down, Direction::Left) {
-                            update_count(&mut next_beams, left, count);
-                        };
-                        if let Some((right, _)) = grid.get_by_direction(&down, Direction::Right) {
-                            update_count(&mut next_beams, right, count);
-                        };
-                    }
-                    '|' => (),
-                    _ => panic!("unknown"),
-                };
-            } else {
-                options += count;
-            }
+    let grid = parse(input);
+    let start = *grid.locations.iter().find(|(_, c)| **c == '|').unwrap().0;
+
+    // the number of timelines of a beam, which splits into two at every splitter below it.
+    let mut timelines = Memo::new();
+    let result = timelines.solve(start, &|recurse, beam: Location| {
+        let below = grid.get_by_direction(&beam, Direction::Down);
+        match below {
+            None => 1,
+            Some((down, '^')) => [Direction::Left, Direction::Right]
+                .into_iter()
+                .filter_map(|direction| grid.get_by_direction(&down, direction))
+                .map(|(side, _)| recurse(side))
+                .sum(),
+            Some((down, '.' | '|')) => recurse(down),
+            Some(_) => panic!("unknown"),
         }
+    });
 
-        for (beam, count) in next_beams.clone() {
-            grid.locations
-                .insert(beam, count.to_string().chars().last().unwrap());
-        }
-
-        if next_beams.is_empty() {
-            break;
-        }
-
-        beams = next_beams;
-    }
-    Some(options)
+    Some(result)
 }
 
 #[cfg(test)]
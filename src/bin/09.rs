@@ -1,4 +1,4 @@
-use advent_of_code::{Direction, Location};
+use advent_of_code::{Location, polygon::RectilinearPolygon};
 use itertools::Itertools;
 
 advent_of_code::solution!(9);
@@ -38,41 +38,14 @@ pub fn part_one(input: &str) -> Option<u64> {
 
 pub fn part_two(input: &str) -> Option<u64> {
     let red_tiles = parse(input);
-    let red_lines: Vec<(Location, Location, Direction)> = red_tiles
-        .clone()
-        .into_iter()
-        .tuple_windows()
-        .map(|(a, b)| {
-            (
-                a,
-                b,
-                if a.x == b.x {
-                    Direction::Right
-                } else {
-                    Direction::Up
-                },
-            )
-        })
-        .collect_vec();
+    let polygon = RectilinearPolygon::new(red_tiles.clone()).expect("red tiles form a loop");
 
     Some(
         red_tiles
             .into_iter()
             .combinations(2)
-            // filter out any squares not within the shape
-            .filter(|locations| {
-                let a = locations.first().unwrap();
-                let b = locations.last().unwrap();
-                let (x1, x2) = if a.x < b.x { (a.x, b.x) } else { (b.x, a.x) };
-                let (y1, y2) = if a.y < b.y { (a.y, b.y) } else { (b.y, a.y) };
-                for x in x1..=x2 {
-                    for y in y1..=y2 {
-                        dbg!((x, y));
-                        // TODO: return false if point is not within shape
-                    }
-                }
-                true
-            })
+            // filter out any rectangles not within the loop of red and green tiles
+            .filter(|locations| polygon.contains_rectangle(&locations[0], &locations[1]))
             .map(|locations| calculate_area(locations.first().unwrap(), locations.last().unwrap()))
             .max()
             .unwrap(),
@@ -1,71 +1,46 @@
-use core::num;
-
+use advent_of_code::{
+    bitset::Bitset64,
+    math::{ilp::minimise_sum, linear::solve_gf2},
+    parse::{
+        Parser, bracketed, lines, list, literal, parse_all, take_while1, unsigned, whitespace,
+    },
+};
 use itertools::Itertools;
 
 advent_of_code::solution!(10);
 
 #[derive(Debug)]
 struct Machine {
-    lights: Vec<char>,
-    buttons: Vec<Vec<usize>>,
+    lights: Bitset64,
+    /// The counters, or lights, every button is wired to.
+    buttons: Vec<Bitset64>,
     joltage: Vec<usize>,
 }
 
-fn parse(input: &str) -> Vec<Machine> {
-    let machines: Vec<Machine> = input
-        .lines()
-        .map(|line| {
-            let lights = line
-                .split_once("] ")
-                .unwrap()
-                .0
-                .chars()
-                .skip(1)
-                .collect_vec();
-            let buttons = line
-                .split_once("] ")
-                .unwrap()
-                .1
-                .split_once(" {")
-                .unwrap()
-                .0
-                .split_whitespace()
-                .map(|button| {
-                    button[1..button.len() - 1]
-                        .split(",")
-                        .map(|number| number.parse().unwrap())
-                        .collect_vec()
-                })
-                .collect_vec();
-            let joltage = line
-                .split_once(" {")
-                .unwrap()
-                .1
-                .replace("}", "")
-                .split(",")
-                .map(|number| number.parse().unwrap())
-                .collect_vec();
+/// Parses lines like `[.##.] (3) (1,3) (2) {3,5,4,7}`.
+fn machine<'a>() -> impl Parser<'a, Machine> {
+    let numbers = || list(unsigned::<usize>(), literal(","));
+    let lights = bracketed("[", take_while1("lights", |c| c == '.' || c == '#'), "]");
+    let buttons = list(bracketed("(", numbers(), ")"), whitespace());
+    let joltage = bracketed("{", numbers(), "}");
 
-            Machine {
-                lights,
-                buttons,
-                joltage,
-            }
+    lights
+        .skip(whitespace())
+        .then(buttons)
+        .skip(whitespace())
+        .then(joltage)
+        .map(|((lights, buttons), joltage)| Machine {
+            lights: lights.parse().unwrap(),
+            buttons: buttons
+                .into_iter()
+                .map(|button| button.into_iter().collect())
+                .collect(),
+            joltage,
         })
-        .collect();
-
-    machines
 }
 
-fn toggle_buttons_matches_light_goal(light: &Vec<char>, buttons: &Vec<&Vec<usize>>) -> bool {
-    let goal: Vec<bool> = light.iter().map(|c| *c == '#').collect_vec();
-    let mut start: Vec<bool> = light.iter().map(|c| false).collect_vec();
-    for button in buttons {
-        for number in button.iter() {
-            start[*number] = !start[*number]
-        }
-    }
-    goal == start
+fn parse(input: &str) -> Vec<Machine> {
+    parse_all(lines(machine()), input).unwrap_or_else(|e| panic!("invalid input: {e}"))
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -74,62 +49,43 @@ pub fn part_one(input: &str) -> Option<u64> {
         machines
             .iter()
             .map(|machine| {
-                let mut presses = 1;
-                loop {
-                    let combination =
-                        machine
-                            .buttons
-                            .iter()
-                            .combinations(presses)
-                            .find(|buttons| {
-                                toggle_buttons_matches_light_goal(&machine.lights, buttons)
-                            });
-
-                    if let Some(combination) = combination {
-                        return combination.len() as u64;
-                    } else {
-                        presses += 1;
-                    }
-                }
+                // one equation per light: the buttons toggling it must add up to its goal.
+                solve_gf2(&machine.buttons, machine.lights)
+                    .expect("lights cannot be reached")
+                    .min_weight()
+                    .count() as u64
             })
             .sum(),
     )
 }
 
-fn toggle_buttons_matches_joltage_goal(joltage: &Vec<usize>, buttons: &Vec<&Vec<usize>>) -> bool {
-    // let goal: Vec<bool> = light.iter().map(|c| *c == '#').collect_vec();
-    let mut start: Vec<usize> = joltage.iter().map(|_| 0).collect_vec();
-    for button in buttons {
-        for number in button.iter() {
-            start[*number] += 1;
-        }
-    }
-    *joltage == start
-}
-
 pub fn part_two(input: &str) -> Option<u64> {
     let machines = parse(input);
     Some(
         machines
             .iter()
             .map(|machine| {
-                let mut presses = 1;
-                loop {
-                    let combination = machine
-                        .buttons
-                        .iter()
-                        .combinations_with_replacement(presses)
-                        // TODO: do something smart here, we know that we need a lot of presses
-                        .find(|buttons| {
-                            toggle_buttons_matches_joltage_goal(&machine.joltage, buttons)
-                        });
+                // one equation per counter: the presses of the buttons increasing it add up to its joltage.
+                let matrix = (0..machine.joltage.len())
+                    .map(|counter| {
+                        machine
+                            .buttons
+                            .iter()
+                            .map(|button| i64::from(button.contains(counter)))
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                let joltage = machine.joltage.iter().map(|j| *j as i64).collect_vec();
+                // a button cannot be pressed more often than the lowest counter it increases.
+                let upper = machine
+                    .buttons
+                    .iter()
+                    .map(|button| button.iter().map(|c| joltage[c]).min().unwrap_or(0))
+                    .collect_vec();
 
-                    if let Some(combination) = combination {
-                        return combination.len() as u64;
-                    } else {
-                        presses += 1;
-                    }
-                }
+                let (presses, _) =
+                    minimise_sum(&matrix, &joltage, &upper).expect("joltage cannot be reached");
+                presses as u64
             })
             .sum(),
     )
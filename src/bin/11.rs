@@ -1,66 +1,55 @@
-use itertools::Itertools;
-use pathfinding::directed::count_paths::count_paths;
-use std::collections::HashMap;
+use advent_of_code::{graph::Graph, interner::Interner};
 
 advent_of_code::solution!(11);
 
-fn parse(input: &str) -> HashMap<String, Vec<String>> {
-    input
-        .lines()
-        .map(|line| {
-            let (input_device, output_devices) = line.split_once(": ").unwrap();
+advent_of_code::line_parser! {
+    struct Device = "{name}: {outputs: ' '}" {
+        name: String,
+        outputs: Vec<String>,
+    }
+}
+
+fn parse(input: &str) -> Graph {
+    let devices = Device::parse_lines(input).unwrap_or_else(|e| panic!("invalid input: {e}"));
 
-            (
-                input_device.to_string(),
-                output_devices
-                    .split_whitespace()
-                    .map(|device| device.to_string())
-                    .collect_vec(),
-            )
+    let mut names = Interner::new();
+    let edges = devices
+        .iter()
+        .flat_map(|device| {
+            let from = names.intern(&device.name);
+            device
+                .outputs
+                .iter()
+                .map(|output| (from, names.intern(output)))
+                .collect::<Vec<_>>()
         })
-        .collect()
+        .collect::<Vec<_>>();
+
+    let mut graph = Graph::with_nodes(names);
+    for (from, to) in edges {
+        graph.connect(from, to);
+    }
+    graph
+}
+
+fn count_paths(graph: &Graph, from: &str, to: &str, waypoints: &[&str]) -> Option<u64> {
+    let names = graph.nodes();
+    let waypoints = waypoints
+        .iter()
+        .map(|name| names.get(name))
+        .collect::<Option<Vec<_>>>()?;
+    let paths = graph
+        .count_paths_via(names.get(from)?, names.get(to)?, &waypoints)
+        .unwrap_or_else(|e| panic!("invalid input: {e}"));
+    Some(paths)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let devices = parse(input);
-    let start: String = "you".to_string();
-    let end: String = "out".to_string();
-    let paths = count_paths(start, |p| devices.get(p).unwrap().clone(), |p| p == &end);
-    Some(paths.try_into().unwrap())
+    count_paths(&parse(input), "you", "out", &[])
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let devices = parse(input);
-    let start: String = "svr".to_string();
-    let mid1: String = "fft".to_string();
-    let mid2: String = "dac".to_string();
-    let end: String = "out".to_string();
-    let no_paths: Vec<String> = vec![];
-
-    // start -> mid1 -> mid2 -> end
-    // there are no path from mid2 to mid1, so we do not have to take that into account
-
-    let start_to_mid1 = count_paths(
-        start.clone(),
-        |p| devices.get(p).unwrap_or(&no_paths).clone(),
-        |p| p == &mid1,
-    );
-    let mid1_to_mid2 = count_paths(
-        mid1.clone(),
-        |p| devices.get(p).unwrap_or(&no_paths).clone(),
-        |p| p == &mid2,
-    );
-    let mid2_to_end = count_paths(
-        mid2.clone(),
-        |p| devices.get(p).unwrap_or(&no_paths).clone(),
-        |p| p == &end,
-    );
-
-    Some(
-        (start_to_mid1 * mid1_to_mid2 * mid2_to_end)
-            .try_into()
-            .unwrap(),
-    )
+    count_paths(&parse(input), "svr", "out", &["fft", "dac"])
 }
 
 #[cfg(test)]
@@ -0,0 +1,449 @@
+//! Small sets of indices stored as bits, for boolean state vectors like rows of lights.
+//!
+//! [`Bitset`] is a fixed-capacity set in a single `u64` or `u128`. It is `Copy`, hashable and
+//! ordered, so states can be BFS nodes or memo keys without allocating. [`BitVec`] grows as
+//! needed and remembers its length.
+//!
+//! ```
+//! use advent_of_code::bitset::Bitset64;
+//!
+//! let lights: Bitset64 = ".##.".parse().unwrap();
+//! let button: Bitset64 = [1, 3].into_iter().collect();
+//! assert_eq!((lights ^ button).to_pattern(4), "..##");
+//! assert_eq!(lights.count(), 2);
+//! assert_eq!(lights.iter().collect::<Vec<_>>(), vec![1, 2]);
+//! ```
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr},
+    str::FromStr,
+};
+
+/// Unsigned integers that can back a [`Bitset`].
+pub trait Word:
+    Copy
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Debug
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+{
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64, u128);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BitsetError {
+    InvalidCharacter(char),
+    TooLong { length: usize, capacity: usize },
+}
+
+impl Display for BitsetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BitsetError::InvalidCharacter(c) => write!(f, "expected `#` or `.`, found `{c}`."),
+            BitsetError::TooLong { length, capacity } => {
+                write!(
+                    f,
+                    "{length} bits do not fit in a bitset of {capacity} bits."
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BitsetError {}
+
+/// Parses a pattern like `.##.`, where `#` is a set bit. The first character is bit 0.
+fn parse_pattern(s: &str) -> Result<Vec<bool>, BitsetError> {
+    s.chars()
+        .map(|c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(BitsetError::InvalidCharacter(c)),
+        })
+        .collect()
+}
+
+/// A set of indices below `W::BITS`, stored in one word.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bitset<W: Word = u64> {
+    bits: W,
+}
+
+pub type Bitset64 = Bitset<u64>;
+pub type Bitset128 = Bitset<u128>;
+
+impl<W: Word> Bitset<W> {
+    pub const CAPACITY: usize = W::BITS;
+
+    pub fn new() -> Self {
+        Self { bits: W::ZERO }
+    }
+
+    pub fn from_bits(bits: W) -> Self {
+        Self { bits }
+    }
+
+    pub fn bits(&self) -> W {
+        self.bits
+    }
+
+    fn mask(index: usize) -> W {
+        assert!(
+            index < W::BITS,
+            "index {index} is out of bounds for a bitset of {} bits",
+            W::BITS
+        );
+        W::ONE << index
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < W::BITS && self.bits & Self::mask(index) != W::ZERO
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.bits = self.bits | Self::mask(index);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.bits = self.bits & !Self::mask(index);
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        self.bits = self.bits ^ Self::mask(index);
+    }
+
+    /// The number of set bits.
+    pub fn count(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == W::ZERO
+    }
+
+    /// The set indices, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + use<W> {
+        let mut bits = self.bits;
+        std::iter::from_fn(move || {
+            if bits == W::ZERO {
+                return None;
+            }
+            let index = bits.trailing_zeros() as usize;
+            bits = bits ^ (W::ONE << index);
+            Some(index)
+        })
+    }
+
+    /// The first `len` bits as a pattern like `.##.`.
+    pub fn to_pattern(&self, len: usize) -> String {
+        (0..len)
+            .map(|i| if self.contains(i) { '#' } else { '.' })
+            .collect()
+    }
+}
+
+impl<W: Word> FromStr for Bitset<W> {
+    type Err = BitsetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = parse_pattern(s)?;
+        if pattern.len() > W::BITS {
+            return Err(BitsetError::TooLong {
+                length: pattern.len(),
+                capacity: W::BITS,
+            });
+        }
+        Ok(pattern
+            .into_iter()
+            .enumerate()
+            .filter(|(_, set)| *set)
+            .map(|(index, _)| index)
+            .collect())
+    }
+}
+
+impl<W: Word> FromIterator<usize> for Bitset<W> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for index in iter {
+            set.insert(index);
+        }
+        set
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        impl<W: Word> $op for Bitset<W> {
+            type Output = Bitset<W>;
+
+            fn $method(self, other: Bitset<W>) -> Bitset<W> {
+                Bitset::from_bits(self.bits.$method(other.bits))
+            }
+        }
+
+        impl<W: Word> $assign for Bitset<W> {
+            fn $assign_method(&mut self, other: Bitset<W>) {
+                *self = (*self).$method(other);
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+
+/* -------------------------------------------------------------------------- */
+
+/// A growable sequence of bits with a length.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitVec {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVec {
+    /// A bit vector of `len` unset bits.
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a bit at the end.
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, bit);
+    }
+
+    fn check(&self, index: usize) {
+        assert!(
+            index < self.len,
+            "index {index} is out of bounds for {} bits",
+            self.len
+        );
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn set(&mut self, index: usize, bit: bool) {
+        self.check(index);
+        let mask = 1 << (index % 64);
+        if bit {
+            self.words[index / 64] |= mask;
+        } else {
+            self.words[index / 64] &= !mask;
+        }
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        self.check(index);
+        self.words[index / 64] ^= 1 << (index % 64);
+    }
+
+    /// The number of set bits.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Whether no bit is set.
+    pub fn none(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// The indices of the set bits, in ascending order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, word)| Bitset::from_bits(*word).iter().map(move |bit| i * 64 + bit))
+    }
+
+    fn combine(&mut self, other: &BitVec, op: impl Fn(u64, u64) -> u64) {
+        assert_eq!(self.len, other.len, "bit vectors differ in length");
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word = op(*word, *other);
+        }
+    }
+}
+
+impl BitXorAssign<&BitVec> for BitVec {
+    fn bitxor_assign(&mut self, other: &BitVec) {
+        self.combine(other, |a, b| a ^ b);
+    }
+}
+
+impl BitAndAssign<&BitVec> for BitVec {
+    fn bitand_assign(&mut self, other: &BitVec) {
+        self.combine(other, |a, b| a & b);
+    }
+}
+
+impl BitOrAssign<&BitVec> for BitVec {
+    fn bitor_assign(&mut self, other: &BitVec) {
+        self.combine(other, |a, b| a | b);
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::default();
+        for bit in iter {
+            bits.push(bit);
+        }
+        bits
+    }
+}
+
+impl FromStr for BitVec {
+    type Err = BitsetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_pattern(s)?.into_iter().collect())
+    }
+}
+
+impl Display for BitVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for index in 0..self.len {
+            write!(f, "{}", if self.get(index) { '#' } else { '.' })?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitVec, Bitset, Bitset64, Bitset128, BitsetError};
+    use std::collections::HashSet;
+
+    #[test]
+    fn sets_and_clears_bits() {
+        let mut set = Bitset64::new();
+        set.insert(3);
+        set.insert(63);
+        set.toggle(5);
+        set.toggle(3);
+        assert!(set.contains(63));
+        assert!(set.contains(5));
+        assert!(!set.contains(3));
+        assert!(!set.contains(200));
+        set.remove(63);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![5]);
+        assert_eq!(set.count(), 1);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: Bitset64 = [0, 1, 2].into_iter().collect();
+        let b: Bitset64 = [2, 3].into_iter().collect();
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), vec![0, 1, 3]);
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![2]);
+        assert_eq!((a | b).count(), 4);
+
+        let mut c = a;
+        c ^= a;
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn parses_patterns() {
+        let set: Bitset128 = "#...#".parse().unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 4]);
+        assert_eq!(set.to_pattern(6), "#...#.");
+        assert_eq!(
+            "#x".parse::<Bitset64>(),
+            Err(BitsetError::InvalidCharacter('x'))
+        );
+        assert_eq!(
+            "#".repeat(65).parse::<Bitset64>(),
+            Err(BitsetError::TooLong {
+                length: 65,
+                capacity: 64
+            })
+        );
+        assert_eq!(Bitset::<u8>::CAPACITY, 8);
+    }
+
+    #[test]
+    fn works_as_a_key() {
+        let states: HashSet<Bitset64> = ["#.", ".#", "#."]
+            .into_iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert_eq!(states.len(), 2);
+        assert!(Bitset64::from_bits(1) < Bitset64::from_bits(2));
+    }
+
+    #[test]
+    fn grows_bit_vectors() {
+        let mut bits: BitVec = "#.#".parse().unwrap();
+        for _ in 0..100 {
+            bits.push(false);
+        }
+        bits.set(70, true);
+        assert_eq!(bits.len(), 103);
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), vec![0, 2, 70]);
+
+        let mut other = BitVec::new(103);
+        other.toggle(2);
+        other.toggle(102);
+        bits ^= &other;
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), vec![0, 70, 102]);
+        assert_eq!(bits.count(), 3);
+        assert!(!bits.none());
+        assert_eq!(BitVec::new(3).to_string(), "...");
+    }
+}
@@ -0,0 +1,283 @@
+//! Coordinate compression for grids with huge, sparse coordinates.
+//!
+//! Every distinct coordinate gets a compressed index of its own, and the values between two
+//! distinct coordinates share one index. A compressed cell therefore stands for a rectangle of
+//! real tiles, and its weight is the number of tiles in that rectangle.
+//!
+//! ```
+//! use advent_of_code::{Location, compress::Compression};
+//!
+//! let corners = [Location { x: 10, y: 100 }, Location { x: 50_000, y: 7 }];
+//! let compression = Compression::new(&corners);
+//! let cell = compression.compress(&Location { x: 20, y: 100 }).unwrap();
+//! assert_eq!(cell, Location { x: 1, y: 2 });
+//! assert_eq!(compression.weight(&cell), 49_989);
+//! ```
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+
+use crate::{Grid, Location};
+
+/// The compression of one axis.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Axis {
+    /// The first value of every compressed index, followed by one past the last value. They are
+    /// `u64`, so `u32::MAX` can have an index of its own.
+    breakpoints: Vec<u64>,
+}
+
+impl Axis {
+    /// Compresses the values from the smallest to the largest value.
+    pub fn new(values: impl IntoIterator<Item = u32>) -> Self {
+        let breakpoints = values
+            .into_iter()
+            .flat_map(|value| [u64::from(value), u64::from(value) + 1])
+            .sorted()
+            .dedup()
+            .collect();
+        Self { breakpoints }
+    }
+
+    /// Compresses the values with an extra index on either side, e.g. to flood fill the outside.
+    pub fn with_margin(values: impl IntoIterator<Item = u32>) -> Self {
+        let mut axis = Self::new(values);
+        if let (Some(first), Some(last)) = (axis.breakpoints.first(), axis.breakpoints.last()) {
+            let (first, last) = (*first, *last);
+            if first > 0 {
+                axis.breakpoints.insert(0, first - 1);
+            }
+            // there are no values after `u32::MAX` to put in a margin.
+            if last <= u64::from(u32::MAX) {
+                axis.breakpoints.push(last + 1);
+            }
+        }
+        axis
+    }
+
+    /// The number of compressed indices.
+    pub fn len(&self) -> usize {
+        self.breakpoints.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The compressed index containing a value, if it lies within the compressed values.
+    pub fn index(&self, value: u32) -> Option<usize> {
+        match self.breakpoints.binary_search(&u64::from(value)) {
+            Ok(index) if index < self.len() => Some(index),
+            Err(index) if index > 0 && index <= self.len() => Some(index - 1),
+            _ => None,
+        }
+    }
+
+    /// The values a compressed index stands for.
+    pub fn range(&self, index: usize) -> RangeInclusive<u32> {
+        self.breakpoints[index] as u32..=(self.breakpoints[index + 1] - 1) as u32
+    }
+
+    /// The number of values a compressed index stands for.
+    pub fn width(&self, index: usize) -> u64 {
+        self.breakpoints[index + 1] - self.breakpoints[index]
+    }
+}
+
+/// The compression of both axes of a set of locations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Compression {
+    pub xs: Axis,
+    pub ys: Axis,
+}
+
+impl Compression {
+    pub fn new<'a>(locations: impl IntoIterator<Item = &'a Location> + Clone) -> Self {
+        Self {
+            xs: Axis::new(locations.clone().into_iter().map(|l| l.x)),
+            ys: Axis::new(locations.into_iter().map(|l| l.y)),
+        }
+    }
+
+    /// Like [`Compression::new`], with a ring of extra cells around the locations.
+    pub fn with_margin<'a>(locations: impl IntoIterator<Item = &'a Location> + Clone) -> Self {
+        Self {
+            xs: Axis::with_margin(locations.clone().into_iter().map(|l| l.x)),
+            ys: Axis::with_margin(locations.into_iter().map(|l| l.y)),
+        }
+    }
+
+    /// The compressed cell containing a real location.
+    pub fn compress(&self, location: &Location) -> Option<Location> {
+        Some(Location {
+            x: self.xs.index(location.x)? as u32,
+            y: self.ys.index(location.y)? as u32,
+        })
+    }
+
+    /// The real location at the top left of a compressed cell.
+    pub fn expand(&self, cell: &Location) -> Location {
+        Location {
+            x: *self.xs.range(cell.x as usize).start(),
+            y: *self.ys.range(cell.y as usize).start(),
+        }
+    }
+
+    /// The real locations a compressed cell stands for, as ranges of x and y.
+    pub fn ranges(&self, cell: &Location) -> (RangeInclusive<u32>, RangeInclusive<u32>) {
+        (
+            self.xs.range(cell.x as usize),
+            self.ys.range(cell.y as usize),
+        )
+    }
+
+    /// The number of real tiles a compressed cell stands for.
+    pub fn weight(&self, cell: &Location) -> u64 {
+        self.xs.width(cell.x as usize) * self.ys.width(cell.y as usize)
+    }
+
+    /// The total number of real tiles the compressed cells stand for.
+    pub fn total_weight<'a>(&self, cells: impl IntoIterator<Item = &'a Location>) -> u64 {
+        cells.into_iter().map(|cell| self.weight(cell)).sum()
+    }
+
+    /// Every compressed cell, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = Location> + '_ {
+        (0..self.ys.len() as u32)
+            .flat_map(|y| (0..self.xs.len() as u32).map(move |x| Location { x, y }))
+    }
+
+    /// Builds a compressed grid, with the value of every cell computed from the cell.
+    pub fn grid<T>(&self, value: impl Fn(&Location) -> Option<T>) -> Grid<T> {
+        Grid {
+            locations: self
+                .cells()
+                .filter_map(|cell| Some((cell, value(&cell)?)))
+                .collect(),
+        }
+    }
+
+    /// A compressed grid holding the weight of every cell.
+    pub fn weights(&self) -> Grid<u64> {
+        self.grid(|cell| Some(self.weight(cell)))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Axis, Compression};
+    use crate::Location;
+    use itertools::Itertools;
+    use std::collections::HashSet;
+
+    #[test]
+    fn compresses_an_axis() {
+        let axis = Axis::new([10, 3, 10, 7]);
+        assert_eq!(axis.len(), 5);
+        assert_eq!(axis.index(3), Some(0));
+        assert_eq!(axis.index(5), Some(1));
+        assert_eq!(axis.index(6), Some(1));
+        assert_eq!(axis.index(7), Some(2));
+        assert_eq!(axis.index(10), Some(4));
+        assert_eq!(axis.index(2), None);
+        assert_eq!(axis.index(11), None);
+        assert_eq!(axis.range(1), 4..=6);
+        assert_eq!(axis.width(3), 2);
+        assert_eq!((0..axis.len()).map(|i| axis.width(i)).sum::<u64>(), 8);
+    }
+
+    #[test]
+    fn adds_margins() {
+        let axis = Axis::with_margin([5, 8]);
+        assert_eq!(axis.index(4), Some(0));
+        assert_eq!(axis.index(9), Some(axis.len() - 1));
+        assert_eq!(Axis::with_margin([0]).index(0), Some(0));
+        assert!(Axis::new([]).is_empty());
+    }
+
+    #[test]
+    fn stops_at_the_largest_value() {
+        let axis = Axis::new([3, u32::MAX]);
+        assert_eq!(axis.len(), 3);
+        assert_eq!(axis.range(1), 4..=u32::MAX - 1);
+        assert_eq!(axis.index(u32::MAX), Some(2));
+        assert_eq!(axis.range(2), u32::MAX..=u32::MAX);
+        assert_eq!(axis.width(2), 1);
+
+        let axis = Axis::with_margin([u32::MAX - 1]);
+        assert_eq!(axis.index(u32::MAX - 1), Some(1));
+        assert_eq!(axis.index(u32::MAX), Some(2));
+        assert_eq!(axis.len(), 3);
+    }
+
+    #[test]
+    fn maps_cells_back() {
+        let locations = [
+            Location { x: 2, y: 1 },
+            Location {
+                x: 90_000,
+                y: 70_000,
+            },
+        ];
+        let compression = Compression::new(&locations);
+
+        let cell = compression.compress(&Location { x: 50, y: 1 }).unwrap();
+        assert_eq!(cell, Location { x: 1, y: 0 });
+        assert_eq!(compression.expand(&cell), Location { x: 3, y: 1 });
+        assert_eq!(compression.ranges(&cell), (3..=89_999, 1..=1));
+        assert_eq!(compression.weight(&cell), 89_997);
+
+        let weights = compression.weights();
+        assert_eq!(weights.locations.len(), 9);
+        assert_eq!(weights.locations.values().sum::<u64>(), 89_999 * 70_000);
+    }
+
+    #[test]
+    fn flood_fills_a_compressed_grid() {
+        // the loop of day 09, drawn on a compressed grid.
+        let corners = [
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ]
+        .map(|(x, y)| Location { x, y });
+        let compression = Compression::with_margin(&corners);
+        let boundary: HashSet<Location> = corners
+            .iter()
+            .circular_tuple_windows()
+            .flat_map(|(a, b)| {
+                let (a, b) = (
+                    compression.compress(a).unwrap(),
+                    compression.compress(b).unwrap(),
+                );
+                (a.x.min(b.x)..=a.x.max(b.x))
+                    .cartesian_product(a.y.min(b.y)..=a.y.max(b.y))
+                    .map(|(x, y)| Location { x, y })
+            })
+            .collect();
+        let grid = compression.grid(|cell| Some(if boundary.contains(cell) { '#' } else { '.' }));
+
+        let mut outside = HashSet::from([Location { x: 0, y: 0 }]);
+        let mut todo = vec![Location { x: 0, y: 0 }];
+        while let Some(cell) = todo.pop() {
+            for (next, value) in grid.get_surrounding_locations(&cell) {
+                if *value == '.' && outside.insert(next) {
+                    todo.push(next);
+                }
+            }
+        }
+
+        let inside = compression
+            .cells()
+            .filter(|cell| !outside.contains(cell))
+            .collect_vec();
+        assert_eq!(compression.total_weight(&inside), 46);
+    }
+}
@@ -0,0 +1,324 @@
+//! Directed graphs with named nodes, for puzzles about wiring, dependencies and paths.
+//!
+//! Node names are kept in an [`Interner`], so nodes are referred to by a compact id. Algorithms
+//! that need a DAG report a [`GraphError::Cycle`] instead of looping or silently miscounting.
+//!
+//! ```
+//! use advent_of_code::graph::Graph;
+//!
+//! let mut graph = Graph::new();
+//! graph.add_edge("svr", "fft");
+//! graph.add_edge("svr", "dac");
+//! graph.add_edge("fft", "dac");
+//! graph.add_edge("dac", "out");
+//!
+//! let (svr, out) = (graph.id("svr").unwrap(), graph.id("out").unwrap());
+//! assert_eq!(graph.count_paths(svr, out), Ok(2));
+//! assert_eq!(graph.count_paths_via(svr, out, &[graph.id("fft").unwrap()]), Ok(1));
+//! ```
+use std::fmt::Display;
+
+use crate::interner::Interner;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// The graph has a cycle through this node.
+    Cycle(String),
+    TooManyWaypoints(usize),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::Cycle(node) => write!(f, "the graph has a cycle through `{node}`."),
+            GraphError::TooManyWaypoints(count) => {
+                write!(f, "at most 16 waypoints are supported, got {count}.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// A directed graph whose nodes are identified by name.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    nodes: Interner,
+    successors: Vec<Vec<usize>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    New,
+    Open,
+    Done,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A graph without edges over the names of an interner, keeping their ids.
+    pub fn with_nodes(nodes: Interner) -> Self {
+        Self {
+            successors: vec![vec![]; nodes.len()],
+            nodes,
+        }
+    }
+
+    /// The id of a node, adding the node if it does not exist yet.
+    pub fn node(&mut self, name: &str) -> usize {
+        let id = self.nodes.intern(name);
+        if id == self.successors.len() {
+            self.successors.push(vec![]);
+        }
+        id
+    }
+
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.connect(from, to);
+    }
+
+    /// Adds an edge between two existing nodes.
+    pub fn connect(&mut self, from: usize, to: usize) {
+        assert!(to < self.len(), "unknown node {to}");
+        self.successors[from].push(to);
+    }
+
+    /// The names of the nodes.
+    pub fn nodes(&self) -> &Interner {
+        &self.nodes
+    }
+
+    /// The id of an existing node.
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.nodes.get(name)
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        self.nodes.resolve(id)
+    }
+
+    pub fn successors(&self, id: usize) -> &[usize] {
+        &self.successors[id]
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The nodes reachable from `starts`, every node after all of its successors.
+    fn post_order(
+        &self,
+        starts: impl IntoIterator<Item = usize>,
+    ) -> Result<Vec<usize>, GraphError> {
+        let mut visits = vec![Visit::New; self.len()];
+        let mut order = vec![];
+
+        for start in starts {
+            if visits[start] != Visit::New {
+                continue;
+            }
+            // each entry is a node and the number of its successors handled so far.
+            let mut stack = vec![(start, 0)];
+            visits[start] = Visit::Open;
+
+            while let Some((node, next)) = stack.last_mut() {
+                let node = *node;
+                match self.successors[node].get(*next) {
+                    Some(successor) => {
+                        *next += 1;
+                        match visits[*successor] {
+                            Visit::New => {
+                                visits[*successor] = Visit::Open;
+                                stack.push((*successor, 0));
+                            }
+                            Visit::Open => {
+                                return Err(GraphError::Cycle(self.name(*successor).to_string()));
+                            }
+                            Visit::Done => {}
+                        }
+                    }
+                    None => {
+                        visits[node] = Visit::Done;
+                        order.push(node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// All nodes, every node before all of its successors.
+    pub fn topological_order(&self) -> Result<Vec<usize>, GraphError> {
+        let mut order = self.post_order(0..self.len())?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Computes a value for `start` from the values of its successors, memoising the value of
+    /// every node reachable from it. `combine` gets a node and the values of its successors.
+    pub fn fold<T>(
+        &self,
+        start: usize,
+        mut combine: impl FnMut(usize, &[&T]) -> T,
+    ) -> Result<T, GraphError> {
+        let mut values: Vec<Option<T>> = (0..self.len()).map(|_| None).collect();
+
+        for node in self.post_order([start])? {
+            let successors = self.successors[node]
+                .iter()
+                .map(|s| values[*s].as_ref().unwrap())
+                .collect::<Vec<_>>();
+            let value = combine(node, &successors);
+            values[node] = Some(value);
+        }
+
+        Ok(values[start].take().unwrap())
+    }
+
+    /// The number of paths from `from` to `to`.
+    pub fn count_paths(&self, from: usize, to: usize) -> Result<u64, GraphError> {
+        self.count_paths_via(from, to, &[])
+    }
+
+    /// The number of paths from `from` to `to` that visit every waypoint, in any order.
+    /// A waypoint that is listed more than once only has to be visited once.
+    pub fn count_paths_via(
+        &self,
+        from: usize,
+        to: usize,
+        waypoints: &[usize],
+    ) -> Result<u64, GraphError> {
+        let mut waypoints = waypoints.to_vec();
+        waypoints.sort_unstable();
+        waypoints.dedup();
+        if waypoints.len() > 16 {
+            return Err(GraphError::TooManyWaypoints(waypoints.len()));
+        }
+        let bit = |node: usize| {
+            waypoints
+                .iter()
+                .position(|w| *w == node)
+                .map_or(0, |i| 1_usize << i)
+        };
+
+        // for every node, the number of paths to `to` by the set of waypoints they visit.
+        let counts = self.fold(from, |node, successors| {
+            let mut counts = vec![0_u64; 1 << waypoints.len()];
+            if node == to {
+                counts[bit(node)] = 1;
+                return counts;
+            }
+            for successor in successors {
+                for (visited, count) in successor.iter().enumerate() {
+                    counts[visited | bit(node)] += count;
+                }
+            }
+            counts
+        })?;
+
+        Ok(counts[(1 << waypoints.len()) - 1])
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Graph, GraphError};
+    use crate::interner::Interner;
+
+    #[cfg(test)]
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let graph = graph(&[("c", "d"), ("a", "b"), ("b", "c"), ("a", "c")]);
+        let order = graph.topological_order().unwrap();
+        let position = |name| order.iter().position(|id| graph.name(*id) == name);
+        assert!(position("a") < position("b"));
+        assert!(position("b") < position("c"));
+        assert!(position("c") < position("d"));
+        assert_eq!(order.len(), 4);
+    }
+
+    #[test]
+    fn builds_from_interned_names() {
+        let mut names = Interner::new();
+        let (a, b, c) = (names.intern("a"), names.intern("b"), names.intern("c"));
+        let mut graph = Graph::with_nodes(names);
+        graph.connect(a, b);
+        graph.connect(b, c);
+        graph.add_edge("a", "d");
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.successors(a), &[b, graph.id("d").unwrap()]);
+        assert_eq!(graph.count_paths(a, c), Ok(1));
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let graph = graph(&[("a", "b"), ("b", "c"), ("c", "a"), ("x", "a")]);
+        assert!(matches!(
+            graph.topological_order(),
+            Err(GraphError::Cycle(_))
+        ));
+        let x = graph.id("x").unwrap();
+        assert!(graph.count_paths(x, graph.id("c").unwrap()).is_err());
+    }
+
+    #[test]
+    fn folds_over_reachable_nodes() {
+        let graph = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("e", "e")]);
+        // the longest path from `a`; the cycle at `e` is not reachable.
+        let longest = graph.fold(graph.id("a").unwrap(), |_, successors| {
+            successors.iter().map(|s| **s + 1).max().unwrap_or(0)
+        });
+        assert_eq!(longest, Ok(2));
+    }
+
+    #[test]
+    fn counts_paths_through_waypoints_in_any_order() {
+        let graph = graph(&[
+            ("s", "a"),
+            ("s", "b"),
+            ("a", "b"),
+            ("b", "a2"),
+            ("a2", "t"),
+            ("b", "t"),
+            ("a", "t"),
+        ]);
+        let id = |name| graph.id(name).unwrap();
+        assert_eq!(graph.count_paths(id("s"), id("t")), Ok(5));
+        assert_eq!(graph.count_paths_via(id("s"), id("t"), &[id("b")]), Ok(4));
+        assert_eq!(
+            graph.count_paths_via(id("s"), id("t"), &[id("a"), id("b")]),
+            Ok(2)
+        );
+        // waypoints in the "wrong" order are found too.
+        assert_eq!(
+            graph.count_paths_via(id("s"), id("t"), &[id("a2"), id("b")]),
+            Ok(2)
+        );
+        assert_eq!(
+            graph.count_paths_via(id("s"), id("t"), &[id("b"), id("b")]),
+            Ok(4)
+        );
+        assert_eq!(graph.count_paths(id("t"), id("s")), Ok(0));
+    }
+}
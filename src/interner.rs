@@ -0,0 +1,109 @@
+//! Interning of names into compact ids, for inputs that refer to things by label.
+//!
+//! Ids are handed out in order of first appearance, so they can index plain `Vec`s and are
+//! cheap to copy around, while [`Interner::resolve`] still gives the name back for display.
+//!
+//! ```
+//! use advent_of_code::interner::Interner;
+//!
+//! let mut names = Interner::new();
+//! let you = names.intern("you");
+//! let out = names.intern("out");
+//! assert_eq!(names.intern("you"), you);
+//! assert_eq!((you, out), (0, 1));
+//! assert_eq!(names.get("out"), Some(out));
+//! assert_eq!(names.resolve(out), "out");
+//! ```
+use std::collections::HashMap;
+
+/// A two-way mapping between names and the ids `0..len()`.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of a name, giving it the next free id if it is new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// The id of a name that was interned before.
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    /// The name of an id. Panics if the id was not handed out by this interner.
+    pub fn resolve(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The ids and their names, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.names.iter().map(String::as_str).enumerate()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Interner {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut interner = Interner::new();
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl<'a> Extend<&'a str> for Interner {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for name in iter {
+            self.intern(name);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Interner;
+
+    #[test]
+    fn interns_in_order_of_appearance() {
+        let mut names: Interner = "b a b c a".split(' ').collect();
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.get("b"), Some(0));
+        assert_eq!(names.get("c"), Some(2));
+        assert_eq!(names.get("d"), None);
+        assert_eq!(names.intern("d"), 3);
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            vec![(0, "b"), (1, "a"), (2, "c"), (3, "d")]
+        );
+    }
+
+    #[test]
+    fn resolves_ids() {
+        let mut names = Interner::new();
+        assert!(names.is_empty());
+        let id = names.intern("svr");
+        assert_eq!(names.resolve(id), "svr");
+    }
+}
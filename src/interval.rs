@@ -0,0 +1,353 @@
+//! Sets of integers stored as disjoint inclusive ranges.
+//!
+//! An [`IntervalSet`] keeps its ranges sorted and merges ranges that overlap or touch, so
+//! `1..=3` and `4..=6` become `1..=6`. Ranges may end at the maximum value of their type.
+//!
+//! ```
+//! use advent_of_code::interval::IntervalSet;
+//!
+//! let fresh: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+//! assert!(fresh.contains(11));
+//! assert!(!fresh.contains(8));
+//! assert_eq!(fresh.len(), 14);
+//! ```
+use std::{collections::BTreeMap, fmt::Debug, ops::RangeInclusive};
+
+/// Integers that can be stored in an [`IntervalSet`].
+pub trait Discrete: Copy + Ord + Debug {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// The next value, or `None` at the maximum.
+    fn successor(self) -> Option<Self>;
+
+    /// The previous value, or `None` at the minimum.
+    fn predecessor(self) -> Option<Self>;
+
+    /// The number of values from `self` up to and including `end`.
+    fn count_to(self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count_to(self, end: Self) -> u128 {
+                    (end as i128 - self as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers, stored as sorted ranges that neither overlap nor touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T: Discrete> {
+    /// Start of each range mapped to its (inclusive) end.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of disjoint ranges in the set.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The number of values in the set. This is a `u128`, as a full `u64` set has 2^64 values.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|(start, end)| start.count_to(*end))
+            .sum()
+    }
+
+    /// The ranges of the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..=*end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, end)| *end >= value)
+    }
+
+    /// Whether every value of `range` is in the set.
+    pub fn contains_range(&self, range: RangeInclusive<T>) -> bool {
+        let (start, end) = range.into_inner();
+        start > end
+            || self
+                .ranges
+                .range(..=start)
+                .next_back()
+                .is_some_and(|(_, e)| *e >= end)
+    }
+
+    /// Adds a range, merging it with the ranges it overlaps or touches. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // a range before `start` that reaches up to `start - 1` is merged.
+        if let Some((s, e)) = self.ranges.range(..=start).next_back()
+            && e.successor().is_none_or(|next| next >= start)
+        {
+            start = *s;
+            end = end.max(*e);
+        }
+
+        // ranges starting within `start..=end + 1` are merged.
+        let merged: Vec<(T, T)> = match end.successor() {
+            Some(next) => self.ranges.range(start..=next),
+            None => self.ranges.range(start..),
+        }
+        .map(|(s, e)| (*s, *e))
+        .collect();
+
+        for (s, e) in merged {
+            self.ranges.remove(&s);
+            end = end.max(e);
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    /// Removes a range, splitting the ranges it partly covers.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let mut overlapping: Vec<(T, T)> = self
+            .ranges
+            .range(start..=end)
+            .map(|(s, e)| (*s, *e))
+            .collect();
+        if let Some((s, e)) = self.ranges.range(..start).next_back()
+            && *e >= start
+        {
+            overlapping.push((*s, *e));
+        }
+
+        for (s, e) in overlapping {
+            self.ranges.remove(&s);
+            if s < start {
+                self.ranges.insert(s, start.predecessor().unwrap());
+            }
+            if e > end {
+                self.ranges.insert(end.successor().unwrap(), e);
+            }
+        }
+    }
+
+    /// The values in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    /// The values in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::new();
+        let mut left = self.iter().peekable();
+        let mut right = other.iter().peekable();
+
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            intersection.insert(start..=end);
+            // advance the range that ends first, as it cannot overlap anything else.
+            if a.end() < b.end() {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        intersection
+    }
+
+    /// The values in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The values within `bounds` that are not in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        let mut complement = Self::new();
+        complement.insert(bounds);
+        complement.difference(self)
+    }
+
+    /// The ranges of values between consecutive ranges of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges
+            .iter()
+            .zip(self.ranges.keys().skip(1))
+            .map(|((_, end), next)| end.successor().unwrap()..=next.predecessor().unwrap())
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::IntervalSet;
+
+    #[cfg(test)]
+    fn set(ranges: &[(u64, u64)]) -> IntervalSet<u64> {
+        ranges.iter().map(|(start, end)| *start..=*end).collect()
+    }
+
+    #[cfg(test)]
+    fn ranges(set: &IntervalSet<u64>) -> Vec<(u64, u64)> {
+        set.iter().map(|r| r.into_inner()).collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        assert_eq!(
+            ranges(&set(&[(3, 5), (10, 14), (16, 20), (12, 18)])),
+            vec![(3, 5), (10, 20)]
+        );
+        assert_eq!(ranges(&set(&[(1, 3), (4, 6)])), vec![(1, 6)]);
+        assert_eq!(ranges(&set(&[(4, 6), (1, 3)])), vec![(1, 6)]);
+        assert_eq!(ranges(&set(&[(1, 3), (5, 6)])), vec![(1, 3), (5, 6)]);
+        assert_eq!(ranges(&set(&[(1, 2), (6, 7), (3, 5)])), vec![(1, 7)]);
+        assert_eq!(ranges(&set(&[(2, 8), (3, 4)])), vec![(2, 8)]);
+    }
+
+    #[test]
+    fn ignores_empty_ranges() {
+        let mut set = set(&[(1, 3)]);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(5..=4);
+        assert_eq!(ranges(&set), vec![(1, 3)]);
+    }
+
+    #[test]
+    fn queries_values_and_length() {
+        let set = set(&[(3, 5), (10, 20)]);
+        assert!(set.contains(3));
+        assert!(set.contains(5));
+        assert!(!set.contains(6));
+        assert!(!set.contains(2));
+        assert!(set.contains_range(11..=20));
+        assert!(!set.contains_range(4..=10));
+        assert_eq!(set.len(), 14);
+        assert_eq!(set.range_count(), 2);
+    }
+
+    #[test]
+    fn removes_ranges() {
+        let mut set = set(&[(1, 10), (20, 30)]);
+        set.remove(4..=6);
+        assert_eq!(ranges(&set), vec![(1, 3), (7, 10), (20, 30)]);
+        set.remove(9..=25);
+        assert_eq!(ranges(&set), vec![(1, 3), (7, 8), (26, 30)]);
+        set.remove(0..=3);
+        assert_eq!(ranges(&set), vec![(7, 8), (26, 30)]);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[(1, 5), (10, 15)]);
+        let b = set(&[(4, 11), (20, 21)]);
+        assert_eq!(ranges(&a.union(&b)), vec![(1, 15), (20, 21)]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![(4, 5), (10, 11)]);
+        assert_eq!(ranges(&a.difference(&b)), vec![(1, 3), (12, 15)]);
+        assert_eq!(ranges(&b.difference(&a)), vec![(6, 9), (20, 21)]);
+        assert_eq!(
+            ranges(&set(&[(1, 5)]).intersection(&set(&[(1, 2), (4, 5)]))),
+            vec![(1, 2), (4, 5)]
+        );
+    }
+
+    #[test]
+    fn finds_gaps_and_complement() {
+        let set = set(&[(3, 5), (8, 8), (10, 20)]);
+        assert_eq!(
+            set.gaps().map(|r| r.into_inner()).collect::<Vec<_>>(),
+            vec![(6, 7), (9, 9)]
+        );
+        assert_eq!(
+            ranges(&set.complement(0..=25)),
+            vec![(0, 2), (6, 7), (9, 9), (21, 25)]
+        );
+        assert_eq!(ranges(&set.complement(4..=12)), vec![(6, 7), (9, 9)]);
+    }
+
+    #[test]
+    fn handles_the_maximum_value() {
+        let mut set = set(&[(u64::MAX - 1, u64::MAX), (0, 0)]);
+        assert!(set.contains(u64::MAX));
+        assert_eq!(set.len(), 3);
+
+        set.insert(u64::MAX - 5..=u64::MAX - 2);
+        assert_eq!(ranges(&set), vec![(0, 0), (u64::MAX - 5, u64::MAX)]);
+
+        set.remove(u64::MAX..=u64::MAX);
+        assert_eq!(ranges(&set), vec![(0, 0), (u64::MAX - 5, u64::MAX - 1)]);
+
+        set.insert(1..=u64::MAX);
+        assert_eq!(ranges(&set), vec![(0, u64::MAX)]);
+        assert_eq!(set.len(), 1 << 64);
+        assert!(set.complement(0..=u64::MAX).is_empty());
+        assert_eq!(set.gaps().count(), 0);
+    }
+
+    #[test]
+    fn handles_signed_values() {
+        let set: IntervalSet<i8> = [i8::MIN..=-1, 0..=i8::MAX].into_iter().collect();
+        assert_eq!(set.range_count(), 1);
+        assert_eq!(set.len(), 256);
+    }
+}
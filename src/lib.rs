@@ -3,7 +3,20 @@ use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display};
 
+pub mod automaton;
+pub mod bitset;
+pub mod compress;
+pub mod graph;
+pub mod interner;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod point;
+pub mod polygon;
+pub mod spatial;
 pub mod template;
+pub mod union_find;
 
 #[derive(PartialEq, Clone, Copy, Debug, Eq, Hash, PartialOrd, Ord)]
 pub struct Location {
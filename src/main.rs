@@ -1,8 +1,10 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, time, verify,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, event_days};
 #[cfg(feature = "today")]
 use std::process;
 
@@ -16,21 +18,33 @@ mod args {
         },
         Read {
             day: Day,
+            refresh: bool,
+        },
+        Examples {
+            day: Day,
+            pick: Option<Vec<usize>>,
+            tests: bool,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            part: u8,
+            template: String,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            wait: bool,
         },
         All {
             release: bool,
         },
+        Verify {
+            release: bool,
+        },
         Time {
             all: bool,
             day: Option<Day>,
@@ -47,6 +61,9 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+            },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
@@ -62,16 +79,27 @@ mod args {
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                refresh: args.contains("--refresh"),
+            },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+                pick: args.opt_value_from_fn("--pick", parse_pick)?,
+                tests: args.contains("--tests"),
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                part: args.opt_value_from_fn("--part", parse_part)?.unwrap_or(1),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| "default".into()),
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
+                wait: args.contains("--wait"),
                 dhat: args.contains("--dhat"),
             },
             #[cfg(feature = "today")]
@@ -93,6 +121,19 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_part(s: &str) -> Result<u8, String> {
+        match s {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            _ => Err(format!("expected part 1 or 2, got {s}.")),
+        }
+    }
+
+    /// Parses a comma-separated list of code block numbers, e.g. `1,4`.
+    fn parse_pick(s: &str) -> Result<Vec<usize>, std::num::ParseIntError> {
+        s.split(',').map(|x| x.trim().parse()).collect()
+    }
 }
 
 fn main() {
@@ -103,37 +144,44 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
+            AppArguments::Verify { release } => verify::handle(release),
             AppArguments::Time { day, all, store } => time::handle(day, all, store),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Read { day, refresh } => read::handle(day, refresh),
+            AppArguments::Examples { day, pick, tests } => examples::handle(day, pick, tests),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                part,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // download first, so examples and answers can be taken from the puzzle.
                 if download {
                     download::handle(day);
                 }
+                scaffold::handle(day, overwrite, part, &template);
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                wait,
+            } => solve::handle(day, release, dhat, submit, wait),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
                         download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(day, false, 1, "default");
+                        read::handle(day, false)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during the event ({} of december). \
+                            Please use `scaffold` with a specific day.",
+                            event_days()
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,13 @@
+//! Math for puzzles that are number theory or linear algebra in disguise.
+//!
+//! ```
+//! use advent_of_code::math::ilp::minimise_sum;
+//!
+//! // buttons adding to counters (0), (0, 1) and (1), to reach counters {3, 5}.
+//! let matrix = vec![vec![1, 1, 0], vec![0, 1, 1]];
+//! assert_eq!(minimise_sum(&matrix, &[3, 5], &[5, 5, 5]), Some((5, vec![0, 3, 2])));
+//! ```
+pub mod ilp;
+pub mod linear;
+pub mod number;
+pub mod rational;
@@ -0,0 +1,221 @@
+//! A small integer linear programming solver for systems with few free variables.
+//!
+//! The system is reduced over the rationals, after which only the free variables are searched,
+//! within their bounds. That is fast when there are a handful of free variables, which is the
+//! usual shape of "press buttons to reach counters" puzzles.
+use itertools::Itertools;
+
+use crate::math::{linear::solve_rational, number::lcm, rational::Rational};
+
+/// A pivot variable with integer coefficients: `x = (constant - Σ coefficients[k] · free[k]) / divisor`.
+struct IntegerPivot {
+    column: usize,
+    divisor: i64,
+    constant: i64,
+    coefficients: Vec<i64>,
+}
+
+struct Search<'a> {
+    pivots: Vec<IntegerPivot>,
+    free: Vec<usize>,
+    upper: &'a [i64],
+    /// For every pivot and free variable, the lowest and highest value the free variables from
+    /// that one on can subtract from the pivot's residual.
+    remaining: Vec<Vec<(i64, i64)>>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl Search<'_> {
+    /// The values of free variable `index` that can still give every pivot a value within its
+    /// bounds, whatever the later free variables are.
+    fn feasible(&self, index: usize, residuals: &[i64]) -> (i64, i64) {
+        let (mut low, mut high) = (0, self.upper[self.free[index]]);
+
+        for (p, (pivot, residual)) in self.pivots.iter().zip(residuals).enumerate() {
+            // 0 <= residual - coefficient · value - rest <= upper · divisor
+            let (rest_low, rest_high) = self.remaining[p].get(index + 1).copied().unwrap_or((0, 0));
+            let coefficient = pivot.coefficients[index];
+            let max_product = residual - rest_low;
+            let min_product = residual - rest_high - self.upper[pivot.column] * pivot.divisor;
+
+            match coefficient.signum() {
+                1 => {
+                    low = low.max(div_ceil(min_product, coefficient));
+                    high = high.min(max_product.div_euclid(coefficient));
+                }
+                -1 => {
+                    low = low.max(div_ceil(-max_product, -coefficient));
+                    high = high.min((-min_product).div_euclid(-coefficient));
+                }
+                _ if min_product > 0 || max_product < 0 => return (1, 0),
+                _ => {}
+            }
+        }
+
+        (low, high)
+    }
+
+    /// Assigns the free variable `index` and up, with `residuals` holding each pivot's
+    /// `constant - Σ coefficients · free` for the free variables assigned so far.
+    fn run(&mut self, index: usize, values: &mut Vec<i64>, residuals: Vec<i64>, free_sum: i64) {
+        if self
+            .best
+            .as_ref()
+            .is_some_and(|(best, _)| free_sum >= *best)
+        {
+            return;
+        }
+
+        if index == self.free.len() {
+            self.evaluate(values, &residuals, free_sum);
+            return;
+        }
+
+        let (low, high) = self.feasible(index, &residuals);
+        for value in low..=high {
+            let next = self
+                .pivots
+                .iter()
+                .zip(&residuals)
+                .map(|(pivot, residual)| residual - pivot.coefficients[index] * value)
+                .collect_vec();
+            values.push(value);
+            self.run(index + 1, values, next, free_sum + value);
+            values.pop();
+        }
+    }
+
+    fn evaluate(&mut self, values: &[i64], residuals: &[i64], free_sum: i64) {
+        let mut solution = vec![0; self.upper.len()];
+        for (column, value) in self.free.iter().zip(values) {
+            solution[*column] = *value;
+        }
+
+        let mut total = free_sum;
+        for (pivot, residual) in self.pivots.iter().zip(residuals) {
+            if residual % pivot.divisor != 0 {
+                return;
+            }
+            let value = residual / pivot.divisor;
+            if value < 0 || value > self.upper[pivot.column] {
+                return;
+            }
+            solution[pivot.column] = value;
+            total += value;
+        }
+
+        if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+            self.best = Some((total, solution));
+        }
+    }
+}
+
+fn div_ceil(a: i64, b: i64) -> i64 {
+    -((-a).div_euclid(b))
+}
+
+/// Finds non-negative integers `x` with `matrix · x = rhs` and `x[i] <= upper[i]` that minimise
+/// `Σ x`. Returns the minimal sum and the solution, or `None` if there is no such solution.
+///
+/// The search enumerates every free variable up to its bound, so it suits systems with few free
+/// variables and small bounds.
+pub fn minimise_sum(matrix: &[Vec<i64>], rhs: &[i64], upper: &[i64]) -> Option<(i64, Vec<i64>)> {
+    let solution = solve_rational(matrix, rhs)?;
+    assert_eq!(
+        upper.len(),
+        solution.variables,
+        "every variable needs a bound"
+    );
+
+    let pivots = solution
+        .pivots
+        .iter()
+        .map(|pivot| {
+            let divisor = pivot
+                .coefficients
+                .iter()
+                .chain([&pivot.constant])
+                .fold(1, |divisor, x| lcm(divisor, x.denominator()));
+            let scale = |x: &Rational| x.numerator() * (divisor / x.denominator());
+            IntegerPivot {
+                column: pivot.column,
+                divisor,
+                constant: scale(&pivot.constant),
+                coefficients: pivot.coefficients.iter().map(scale).collect(),
+            }
+        })
+        .collect_vec();
+
+    let remaining = pivots
+        .iter()
+        .map(|pivot| {
+            let mut remaining = vec![(0, 0); solution.free.len()];
+            let mut sum = (0, 0);
+            for (k, column) in solution.free.iter().enumerate().rev() {
+                let product = pivot.coefficients[k] * upper[*column];
+                sum = (sum.0 + product.min(0), sum.1 + product.max(0));
+                remaining[k] = sum;
+            }
+            remaining
+        })
+        .collect_vec();
+
+    let residuals = pivots.iter().map(|pivot| pivot.constant).collect_vec();
+    let mut search = Search {
+        pivots,
+        free: solution.free,
+        upper,
+        remaining,
+        best: None,
+    };
+    search.run(0, &mut vec![], residuals, 0);
+    search.best
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::minimise_sum;
+
+    #[test]
+    fn minimises_button_presses() {
+        // buttons (3) (1,3) (2) (2,3) (0,2) (0,1) reaching counters {3,5,4,7}.
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let counters = [3, 5, 4, 7];
+        let matrix = (0..counters.len())
+            .map(|counter| {
+                buttons
+                    .iter()
+                    .map(|button| i64::from(button.contains(&counter)))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+
+        let (presses, solution) = minimise_sum(&matrix, &counters, &[7; 6]).unwrap();
+        assert_eq!(presses, 10);
+        for (counter, target) in counters.iter().enumerate() {
+            let reached: i64 = (0..buttons.len())
+                .filter(|b| buttons[*b].contains(&counter))
+                .map(|b| solution[b])
+                .sum();
+            assert_eq!(reached, *target);
+        }
+    }
+
+    #[test]
+    fn respects_integrality_and_bounds() {
+        // 2x = 3 has no integer solution.
+        assert_eq!(minimise_sum(&[vec![2]], &[3], &[10]), None);
+        // x + y = 4 with x <= 1, where every solution has the same sum.
+        let (presses, solution) = minimise_sum(&[vec![1, 1]], &[4], &[1, 10]).unwrap();
+        assert_eq!(presses, 4);
+        assert!(solution[0] <= 1);
+        assert_eq!(minimise_sum(&[vec![1, 1]], &[4], &[1, 2]), None);
+        // x - y = 2, minimal at y = 0.
+        assert_eq!(
+            minimise_sum(&[vec![1, -1]], &[2], &[10, 10]),
+            Some((2, vec![2, 0]))
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Gaussian elimination over GF(2) and over the rationals.
+//!
+//! Both solvers reduce the system to reduced row echelon form and describe every solution by a
+//! value for each free variable, so callers can enumerate the solutions they care about.
+use itertools::Itertools;
+
+use crate::{
+    bitset::{BitVec, Bitset64},
+    math::rational::Rational,
+};
+
+/// The solutions of a system over GF(2): a particular solution plus any combination of the
+/// null space vectors. Every vector holds the variables that are set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gf2Solution {
+    pub particular: Bitset64,
+    /// One vector per free variable.
+    pub null_space: Vec<Bitset64>,
+}
+
+impl Gf2Solution {
+    /// Every solution of the system, 2^(free variables) in total.
+    pub fn solutions(&self) -> impl Iterator<Item = Bitset64> + '_ {
+        (0..1_u64 << self.null_space.len()).map(|mask| {
+            self.null_space
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| mask & (1 << index) != 0)
+                .fold(self.particular, |solution, (_, vector)| solution ^ *vector)
+        })
+    }
+
+    /// The solution with the fewest variables set.
+    pub fn min_weight(&self) -> Bitset64 {
+        self.solutions()
+            .min_by_key(|solution| solution.count())
+            .unwrap()
+    }
+}
+
+/// Solves a system over GF(2), where addition is XOR. Every variable is given by the set of
+/// equations it appears in, and `goal` holds the equations whose right hand side is 1, like the
+/// lights a set of buttons toggles and the lights that should end up on.
+/// Returns `None` if the system has no solution.
+pub fn solve_gf2(variables: &[Bitset64], goal: Bitset64) -> Option<Gf2Solution> {
+    assert!(variables.len() <= 64, "at most 64 variables are supported");
+    let equations = variables
+        .iter()
+        .chain([&goal])
+        .filter_map(|set| set.iter().max())
+        .max()
+        .map_or(0, |last| last + 1);
+    let mut rows = (0..equations)
+        .map(|equation| {
+            variables
+                .iter()
+                .map(|variable| variable.contains(equation))
+                .chain([goal.contains(equation)])
+                .collect::<BitVec>()
+        })
+        .collect_vec();
+    let variables = variables.len();
+
+    let mut pivots = vec![];
+    for column in 0..variables {
+        let Some(found) = (pivots.len()..rows.len()).find(|r| rows[*r].get(column)) else {
+            continue;
+        };
+        rows.swap(pivots.len(), found);
+        let pivot = rows[pivots.len()].clone();
+        for (index, row) in rows.iter_mut().enumerate() {
+            if index != pivots.len() && row.get(column) {
+                *row ^= &pivot;
+            }
+        }
+        pivots.push(column);
+    }
+
+    // a zero row with a non-zero right hand side means 0 = 1.
+    if rows[pivots.len()..].iter().any(|row| row.get(variables)) {
+        return None;
+    }
+
+    let particular = pivots
+        .iter()
+        .enumerate()
+        .filter(|(row, _)| rows[*row].get(variables))
+        .map(|(_, column)| *column)
+        .collect();
+
+    let null_space = (0..variables)
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            let mut vector = Bitset64::new();
+            vector.insert(free);
+            for (row, column) in pivots.iter().enumerate() {
+                if rows[row].get(free) {
+                    vector.insert(*column);
+                }
+            }
+            vector
+        })
+        .collect();
+
+    Some(Gf2Solution {
+        particular,
+        null_space,
+    })
+}
+
+/// A pivot variable written in terms of the free variables:
+/// `x = constant - Σ coefficients[k] · free[k]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PivotExpression {
+    pub column: usize,
+    pub constant: Rational,
+    pub coefficients: Vec<Rational>,
+}
+
+/// The solutions of a system over the rationals, parameterised by the free variables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearSolution {
+    pub variables: usize,
+    /// The columns of the free variables, in order.
+    pub free: Vec<usize>,
+    pub pivots: Vec<PivotExpression>,
+}
+
+impl LinearSolution {
+    /// Whether the system has exactly one solution.
+    pub fn is_unique(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// The solution for the given values of the free variables.
+    pub fn evaluate(&self, free_values: &[Rational]) -> Vec<Rational> {
+        let mut solution = vec![Rational::ZERO; self.variables];
+        for (column, value) in self.free.iter().zip(free_values) {
+            solution[*column] = *value;
+        }
+        for pivot in &self.pivots {
+            solution[pivot.column] = pivot
+                .coefficients
+                .iter()
+                .zip(free_values)
+                .fold(pivot.constant, |value, (coefficient, free)| {
+                    value - *coefficient * *free
+                });
+        }
+        solution
+    }
+}
+
+/// Solves `matrix · x = rhs` over the rationals. Returns `None` if the system has no solution.
+pub fn solve_rational(matrix: &[Vec<i64>], rhs: &[i64]) -> Option<LinearSolution> {
+    let variables = matrix.first().map_or(0, |row| row.len());
+    let mut rows = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, value)| {
+            row.iter()
+                .chain([value])
+                .map(|x| Rational::from(*x))
+                .collect_vec()
+        })
+        .collect_vec();
+
+    let mut pivots = vec![];
+    for column in 0..variables {
+        let Some(found) = (pivots.len()..rows.len()).find(|r| !rows[*r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(pivots.len(), found);
+
+        let scale = rows[pivots.len()][column];
+        let pivot = rows[pivots.len()].iter().map(|x| *x / scale).collect_vec();
+        for (index, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if index != pivots.len() && !factor.is_zero() {
+                for (x, p) in row.iter_mut().zip(&pivot) {
+                    *x = *x - factor * *p;
+                }
+            }
+        }
+        rows[pivots.len()] = pivot;
+        pivots.push(column);
+    }
+
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| !row[variables].is_zero())
+    {
+        return None;
+    }
+
+    let free = (0..variables)
+        .filter(|column| !pivots.contains(column))
+        .collect_vec();
+    let pivots = pivots
+        .iter()
+        .enumerate()
+        .map(|(row, column)| PivotExpression {
+            column: *column,
+            constant: rows[row][variables],
+            coefficients: free.iter().map(|f| rows[row][*f]).collect(),
+        })
+        .collect();
+
+    Some(LinearSolution {
+        variables,
+        free,
+        pivots,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{solve_gf2, solve_rational};
+    use crate::{bitset::Bitset64, math::rational::Rational};
+
+    #[cfg(test)]
+    fn set(indices: &[usize]) -> Bitset64 {
+        indices.iter().copied().collect()
+    }
+
+    #[test]
+    fn solves_over_gf2() {
+        // x0 ^ x1 = 1, x1 ^ x2 = 0
+        let variables = [set(&[0]), set(&[0, 1]), set(&[1])];
+        let solution = solve_gf2(&variables, set(&[0])).unwrap();
+        assert_eq!(solution.null_space.len(), 1);
+
+        let solutions: Vec<_> = solution.solutions().collect();
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions.contains(&set(&[0])));
+        assert!(solutions.contains(&set(&[1, 2])));
+        assert_eq!(solution.min_weight(), set(&[0]));
+    }
+
+    #[test]
+    fn detects_inconsistent_gf2_systems() {
+        let variables = [set(&[0, 1]), set(&[0, 1])];
+        assert_eq!(solve_gf2(&variables, set(&[0])), None);
+    }
+
+    #[test]
+    fn solves_over_the_rationals() {
+        // 2x + y = 5, x - y = 1
+        let solution = solve_rational(&[vec![2, 1], vec![1, -1]], &[5, 1]).unwrap();
+        assert!(solution.is_unique());
+        assert_eq!(
+            solution.evaluate(&[]),
+            vec![Rational::from(2), Rational::from(1)]
+        );
+
+        // 2x = 1
+        let solution = solve_rational(&[vec![2]], &[1]).unwrap();
+        assert_eq!(solution.evaluate(&[]), vec![Rational::new(1, 2)]);
+    }
+
+    #[test]
+    fn parameterises_free_variables() {
+        // x + y + z = 6, y - z = 1
+        let solution = solve_rational(&[vec![1, 1, 1], vec![0, 1, -1]], &[6, 1]).unwrap();
+        assert_eq!(solution.free, vec![2]);
+        assert_eq!(
+            solution.evaluate(&[Rational::from(1)]),
+            vec![Rational::from(3), Rational::from(2), Rational::from(1)]
+        );
+        assert_eq!(solve_rational(&[vec![1, 1], vec![2, 2]], &[1, 3]), None);
+    }
+}
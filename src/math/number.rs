@@ -0,0 +1,241 @@
+//! Digits and number theory on plain integers, without going through strings.
+//!
+//! ```
+//! use advent_of_code::math::number::{concat, digit_count, repeat};
+//!
+//! assert_eq!(digit_count(4096), 4);
+//! assert_eq!(concat(12, 345), 12345);
+//! assert_eq!(repeat(56, 2, 3), Some(565656));
+//! ```
+
+/// The number of decimal digits of `n`, where 0 has one digit.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// The decimal digits of `n`, most significant first.
+pub fn digits(n: u64) -> impl Iterator<Item = u8> {
+    (0..digit_count(n))
+        .rev()
+        .map(move |position| (n / 10_u64.pow(position) % 10) as u8)
+}
+
+/// The number with the given decimal digits, most significant first.
+pub fn from_digits(digits: impl IntoIterator<Item = u8>) -> u64 {
+    digits
+        .into_iter()
+        .fold(0, |n, digit| n * 10 + u64::from(digit))
+}
+
+/// The digits of `a` followed by the digits of `b`, so `concat(12, 3) == 123`.
+pub fn concat(a: u64, b: u64) -> u64 {
+    a * 10_u64.pow(digit_count(b)) + b
+}
+
+/// The number made of `n` ones, like 1111 for `n = 4`, or `None` if it does not fit in a `u64`.
+pub fn repunit(n: u32) -> Option<u64> {
+    repeat_multiplier(1, n)
+}
+
+/// The multiplier that repeats a block of `width` digits `times` times, like 10101 for a width
+/// of 2 repeated 3 times, or `None` if it does not fit in a `u64`.
+pub fn repeat_multiplier(width: u32, times: u32) -> Option<u64> {
+    (1..times).try_fold(u64::from(times > 0), |multiplier, _| {
+        multiplier
+            .checked_mul(10_u64.checked_pow(width)?)?
+            .checked_add(1)
+    })
+}
+
+/// `pattern`, written with `width` digits (padded with leading zeros), repeated `times` times,
+/// or `None` if the result does not fit in a `u64`.
+pub fn repeat(pattern: u64, width: u32, times: u32) -> Option<u64> {
+    pattern.checked_mul(repeat_multiplier(width, times)?)
+}
+
+/// The greatest common divisor of `a` and `b`, which is unsigned as `gcd(i64::MIN, 0)` is 2^63.
+pub fn gcd(a: i64, b: i64) -> u64 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`. Panics if it does not fit in an `i64`.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a.unsigned_abs() / gcd(a, b))
+        .checked_mul(b.unsigned_abs())
+        .and_then(|lcm| i64::try_from(lcm).ok())
+        .expect("the least common multiple does not fit in an i64")
+}
+
+/// `(g, x, y)` with `g = gcd(a, b) = a·x + b·y`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// `base^exponent mod modulus`.
+pub fn mod_pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let modulus = u128::from(modulus);
+    let (mut base, mut exponent, mut result) = (u128::from(base) % modulus, exponent, 1 % modulus);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as u64
+}
+
+/// The `x` in `0..modulus` with `a·x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every pair at once, returning the smallest
+/// non-negative `x` and the modulus of the combined congruence. The moduli do not have to be
+/// coprime; `None` means the congruences contradict each other or their combined modulus does
+/// not fit in an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(residue, modulus), (r, m)| {
+            let (g, p, _) = extended_gcd(modulus, *m);
+            let difference = r - residue;
+            if difference % g != 0 {
+                return None;
+            }
+            let combined = (modulus / g).checked_mul(*m)?;
+            let step = i128::from(difference / g) * i128::from(p) % i128::from(m / g);
+            let x = i128::from(residue) + i128::from(modulus) * step;
+            Some((x.rem_euclid(i128::from(combined)) as i64, combined))
+        })
+}
+
+/// The divisors of `n`, in ascending order, built from its prime factors.
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return vec![];
+    }
+    let (mut divisors, mut rest, mut factor) = (vec![1], n, 2);
+    // `factor * factor <= rest` would overflow for factors from 2^32 on.
+    while factor <= rest / factor {
+        let (smaller, mut power) = (divisors.len(), 1);
+        while rest.is_multiple_of(factor) {
+            rest /= factor;
+            power *= factor;
+            let start = divisors.len();
+            divisors.extend_from_within(..smaller);
+            divisors[start..].iter_mut().for_each(|d| *d *= power);
+        }
+        factor += 1;
+    }
+    if rest > 1 {
+        let start = divisors.len();
+        divisors.extend_from_within(..);
+        divisors[start..].iter_mut().for_each(|d| *d *= rest);
+    }
+    divisors.sort_unstable();
+    divisors
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_digits() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(digits(907).collect::<Vec<_>>(), vec![9, 0, 7]);
+        assert_eq!(digits(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(from_digits([9, 0, 7]), 907);
+        assert_eq!(concat(9, 0), 90);
+        assert_eq!(concat(0, 42), 42);
+    }
+
+    #[test]
+    fn repeats_patterns() {
+        assert_eq!(repunit(4), Some(1111));
+        assert_eq!(repunit(20), Some(11111111111111111111));
+        assert_eq!(repunit(21), None);
+        assert_eq!(repeat_multiplier(3, 2), Some(1001));
+        assert_eq!(repeat_multiplier(10, 2), Some(10000000001));
+        assert_eq!(repeat_multiplier(20, 1), Some(1));
+        assert_eq!(repeat_multiplier(20, 2), None);
+        assert_eq!(repeat(824, 3, 3), Some(824824824));
+        assert_eq!(repeat(5, 2, 2), Some(505));
+        assert_eq!(repeat(9, 1, 20), None);
+    }
+
+    #[test]
+    fn does_number_theory() {
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(16), vec![1, 2, 4, 8, 16]);
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(0), vec![]);
+        assert_eq!(divisors(97), vec![1, 97]);
+        assert_eq!(gcd(i64::MIN, 0), 1 << 63);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(crt(&[(0, 1 << 40), (1, (1 << 40) - 1)]), None);
+    }
+
+    #[test]
+    fn finds_divisors_near_the_largest_number() {
+        // u64::MAX = 3 · 5 · 17 · 257 · 641 · 65537 · 6700417.
+        let all = divisors(u64::MAX);
+        assert_eq!(all.len(), 128);
+        assert_eq!(all[..4], [1, 3, 5, 15]);
+        assert_eq!(all.last(), Some(&u64::MAX));
+        assert!(all.iter().all(|d| u64::MAX.is_multiple_of(*d)));
+
+        // (2^32 - 1)^2 = 3^2 · 5^2 · 17^2 · 257^2 · 65537^2.
+        let square = u64::from(u32::MAX).pow(2);
+        let all = divisors(square);
+        assert_eq!(all.len(), 3_usize.pow(5));
+        assert!(all.contains(&u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli that are not coprime.
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,161 @@
+//! Memoisation for recursive solutions with overlapping subproblems.
+//!
+//! [`Memo::solve`] hands the step function a `recurse` callback, so the recursion reads like the
+//! plain recursive definition while every key is computed once. Keys can be anything hashable,
+//! like a [`Location`](crate::Location), a tuple or an interned id.
+//!
+//! ```
+//! use advent_of_code::memo::Memo;
+//!
+//! let mut memo = Memo::new();
+//! let fibonacci = memo.solve(90_u64, &|recurse, n| match n {
+//!     0 | 1 => n,
+//!     _ => recurse(n - 1) + recurse(n - 2),
+//! });
+//! assert_eq!(fibonacci, 2_880_067_194_370_816_120);
+//! assert_eq!(memo.len(), 91);
+//! ```
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+/// The callback a step function uses to get the value of another key.
+pub type Recurse<'a, K, V> = &'a mut dyn FnMut(K) -> V;
+
+/// A cache of computed values, which counts how often it was useful.
+#[derive(Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The value for `key`, computed by `step` unless it is cached. `step` gets a callback to
+    /// get the values of other keys, through this cache too.
+    pub fn solve(&mut self, key: K, step: &dyn Fn(Recurse<'_, K, V>, K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = step(&mut |next| self.solve(next, step), key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The value for `key`, computed by `compute` unless it is cached. `compute` gets the cache
+    /// itself, for recursive functions that take the cache as an argument.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// The number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// How many lookups were answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// How many lookups had to compute their value.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reports the size of the cache instead of its contents, which are rarely readable.
+impl<K, V> Debug for Memo<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Memo")
+            .field("entries", &self.cache.len())
+            .field("hits", &self.hits)
+            .field("misses", &self.misses)
+            .finish()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Memo;
+    use crate::Location;
+
+    #[test]
+    fn computes_every_key_once() {
+        // the number of monotone lattice paths to the origin.
+        let mut memo: Memo<Location, u64> = Memo::new();
+        let paths = memo.solve(Location { x: 16, y: 16 }, &|recurse, l| match (l.x, l.y) {
+            (0, _) | (_, 0) => 1,
+            (x, y) => recurse(Location { x: x - 1, y }) + recurse(Location { x, y: y - 1 }),
+        });
+        assert_eq!(paths, 601_080_390);
+        assert_eq!(memo.misses(), memo.len());
+        assert_eq!(memo.len(), 17 * 17 - 1);
+        assert_eq!(memo.get(&Location { x: 1, y: 1 }), Some(&2));
+    }
+
+    #[test]
+    fn passes_the_cache_to_recursive_functions() {
+        fn collatz(memo: &mut Memo<u64, u32>, n: u64) -> u32 {
+            memo.get_or_insert_with(n, |memo| match n {
+                1 => 0,
+                _ if n % 2 == 0 => 1 + collatz(memo, n / 2),
+                _ => 1 + collatz(memo, 3 * n + 1),
+            })
+        }
+        let mut memo = Memo::new();
+        assert_eq!(collatz(&mut memo, 27), 111);
+        assert_eq!(collatz(&mut memo, 27), 111);
+        assert_eq!(memo.hits(), 1);
+    }
+
+    #[test]
+    fn reports_its_size() {
+        let mut memo: Memo<(u8, u8), u8> = Memo::new();
+        memo.solve((1, 2), &|_, (a, b)| a + b);
+        memo.solve((1, 2), &|_, (a, b)| a + b);
+        assert_eq!(
+            format!("{memo:?}"),
+            "Memo { entries: 1, hits: 1, misses: 1 }"
+        );
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+}
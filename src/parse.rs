@@ -0,0 +1,426 @@
+//! Small parser combinators for puzzle inputs.
+//!
+//! Parsers are functions from an [`Input`] to the parsed value and the remaining input. They are
+//! combined with the functions of this module and the methods of [`Parser`], and run on a whole
+//! input with [`parse_all`]. Failures report the line and column of the offending input.
+//!
+//! ```
+//! use advent_of_code::parse::{Parser, list, literal, parse_all, unsigned, word};
+//!
+//! let line = word().skip(literal(": ")).then(list(unsigned::<u32>(), literal(",")));
+//! assert_eq!(parse_all(line, "abc: 1,2,3"), Ok(("abc", vec![1, 2, 3])));
+//! ```
+use std::{error::Error, fmt::Display, str::FromStr};
+
+pub mod pattern;
+
+/// The remaining input of a parser, along with its position in the whole input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Input<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self { source, offset: 0 }
+    }
+
+    /// The input that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offset == self.source.len()
+    }
+
+    /// Consumes `len` bytes and returns them.
+    pub fn take(self, len: usize) -> (&'a str, Input<'a>) {
+        let taken = &self.rest()[..len];
+        let rest = Input {
+            source: self.source,
+            offset: self.offset + len,
+        };
+        (taken, rest)
+    }
+
+    /// Creates an error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let before = &self.source[..self.offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map_or(before, |i| &before[i + 1..])
+            .chars()
+            .count()
+            + 1;
+
+        let found = match self.rest().lines().next() {
+            Some(rest) if !rest.is_empty() => {
+                format!("`{}`", rest.chars().take(12).collect::<String>())
+            }
+            Some(_) => "end of line".into(),
+            None => "end of input".into(),
+        };
+
+        ParseError {
+            line,
+            column,
+            offset: self.offset,
+            expected: expected.into(),
+            found,
+        }
+    }
+}
+
+/// A parse failure, positioned at 1-based line and column of the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    offset: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+/// A parser producing values of type `T`. Implemented for all functions `Fn(Input) -> ParseResult`.
+pub trait Parser<'a, T>: Sized {
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T>;
+
+    /// Transforms the parsed value.
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+        move |input: Input<'a>| self.parse(input).map(|(value, rest)| (f(value), rest))
+    }
+
+    /// Transforms the parsed value with a fallible function, failing with `expected`.
+    fn try_map<U, E>(self, expected: &str, f: impl Fn(T) -> Result<U, E>) -> impl Parser<'a, U> {
+        let expected = expected.to_string();
+        move |input: Input<'a>| {
+            let (value, rest) = self.parse(input)?;
+            f(value)
+                .map(|value| (value, rest))
+                .map_err(|_| input.error(expected.clone()))
+        }
+    }
+
+    /// Runs `next` after this parser and returns both values.
+    fn then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, (T, U)> {
+        move |input: Input<'a>| {
+            let (a, rest) = self.parse(input)?;
+            let (b, rest) = next.parse(rest)?;
+            Ok(((a, b), rest))
+        }
+    }
+
+    /// Runs `next` after this parser and discards its value.
+    fn skip<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T> {
+        self.then(next).map(|(a, _)| a)
+    }
+
+    /// Tries `other` if this parser fails without consuming input.
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T> {
+        move |input: Input<'a>| match self.parse(input) {
+            Err(e) if e.offset == input.offset => other.parse(input),
+            result => result,
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Runs a parser on a whole input. Only trailing whitespace may be left over.
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, source: &'a str) -> Result<T, ParseError> {
+    let (value, rest) = parser.parse(Input::new(source))?;
+    if rest.rest().trim().is_empty() {
+        Ok(value)
+    } else {
+        Err(rest.error("end of input"))
+    }
+}
+
+/// The start of every line of an input, for parsers that work line by line.
+pub fn line_starts(source: &str) -> impl Iterator<Item = Input<'_>> {
+    let mut offset = 0;
+    source.split_inclusive('\n').map(move |line| {
+        let input = Input { source, offset };
+        offset += line.len();
+        input
+    })
+}
+
+/// Matches a literal string.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest().starts_with(expected) {
+            Ok(input.take(expected.len()))
+        } else {
+            Err(input.error(format!("`{expected}`")))
+        }
+    }
+}
+
+/// Consumes the longest prefix whose characters satisfy `predicate`, which may be empty.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let len = input
+            .rest()
+            .find(|c| !predicate(c))
+            .unwrap_or(input.rest().len());
+        Ok(input.take(len))
+    }
+}
+
+/// Like [`take_while`], but fails with `expected` if nothing matches.
+pub fn take_while1<'a>(
+    expected: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    let inner = take_while(predicate);
+    move |input: Input<'a>| match inner.parse(input)? {
+        ("", _) => Err(input.error(expected)),
+        result => Ok(result),
+    }
+}
+
+/// A run of alphanumeric characters or underscores.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a word", |c| c.is_alphanumeric() || c == '_')
+}
+
+/// One or more spaces or tabs.
+pub fn whitespace<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("whitespace", |c| c == ' ' || c == '\t')
+}
+
+/// A line break, `\n` or `\r\n`.
+pub fn newline<'a>() -> impl Parser<'a, &'a str> {
+    literal("\n").or(literal("\r\n"))
+}
+
+/// An empty line between two blocks, i.e. two line breaks.
+pub fn blank_line<'a>() -> impl Parser<'a, (&'a str, &'a str)> {
+    newline().then(newline())
+}
+
+/// An unsigned decimal number, e.g. `42`.
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, T> {
+    take_while1("a number", |c| c.is_ascii_digit()).try_map("a number in range", str::parse)
+}
+
+/// A decimal number with an optional sign, e.g. `-42` or `+42`.
+pub fn signed<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let sign_len = usize::from(input.rest().starts_with(['-', '+']));
+        let digits = input.rest()[sign_len..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.rest().len() - sign_len);
+
+        if digits == 0 {
+            return Err(input.error("a number"));
+        }
+
+        let (number, rest) = input.take(sign_len + digits);
+        number
+            .trim_start_matches('+')
+            .parse()
+            .map(|value| (value, rest))
+            .map_err(|_| input.error("a number in range"))
+    }
+}
+
+/// An unsigned number in the given radix, e.g. `ff` in radix 16.
+pub fn unsigned_radix<'a>(radix: u32) -> impl Parser<'a, u64> {
+    take_while1("a number", move |c| c.is_digit(radix))
+        .try_map("a number in range", move |s| u64::from_str_radix(s, radix))
+}
+
+/// One or more `item`s separated by `separator`.
+/// Stops before a separator that is not followed by another item.
+pub fn list<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut rest) = item.parse(input)?;
+        let mut items = vec![first];
+
+        loop {
+            let Ok((_, after_separator)) = separator.parse(rest) else {
+                break;
+            };
+            match item.parse(after_separator) {
+                Ok((next, after_item)) => {
+                    items.push(next);
+                    rest = after_item;
+                }
+                // the item started but is malformed, which is an error rather than the end of the list.
+                Err(e) if e.offset > after_separator.offset => return Err(e),
+                Err(_) => break,
+            }
+        }
+
+        Ok((items, rest))
+    }
+}
+
+/// `inner` enclosed by `open` and `close`, e.g. `(1,2)`.
+pub fn bracketed<'a, T>(
+    open: &'static str,
+    inner: impl Parser<'a, T>,
+    close: &'static str,
+) -> impl Parser<'a, T> {
+    literal(open)
+        .then(inner)
+        .skip(literal(close))
+        .map(|(_, value)| value)
+}
+
+/// A `key` and a `value`, divided by `separator`, e.g. `a: b c`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    separator: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    key.skip(literal(separator)).then(value)
+}
+
+/// Succeeds with `None` if `parser` fails without consuming input.
+pub fn optional<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    parser
+        .map(Some)
+        .or(move |input: Input<'a>| Ok((None, input)))
+}
+
+/// One `item` per line.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    list(item, newline())
+}
+
+/// Sections separated by blank lines, e.g. the rules and updates of a puzzle.
+pub fn sections<'a, T>(section: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    list(section, blank_line())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        Parser, bracketed, key_value, lines, list, literal, optional, parse_all, sections, signed,
+        unsigned, unsigned_radix, whitespace, word,
+    };
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse_all(unsigned::<u64>(), "4711\n"), Ok(4711));
+        assert_eq!(parse_all(signed::<i32>(), "-12"), Ok(-12));
+        assert_eq!(parse_all(signed::<i32>(), "+12"), Ok(12));
+        assert_eq!(parse_all(unsigned_radix(16), "ff"), Ok(255));
+        assert_eq!(parse_all(unsigned_radix(2), "101"), Ok(5));
+    }
+
+    #[test]
+    fn reports_out_of_range_numbers() {
+        let error = parse_all(unsigned::<u8>(), "256").unwrap_err();
+        assert_eq!(error.expected, "a number in range");
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+
+    #[test]
+    fn parses_lists() {
+        let numbers = list(unsigned::<u32>(), literal(","));
+        assert_eq!(parse_all(numbers, "1,2,3"), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn stops_lists_before_trailing_separators() {
+        let numbers = list(unsigned::<u32>(), literal(" ")).skip(literal(" x"));
+        assert_eq!(parse_all(numbers, "1 2 x"), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn parses_bracketed_groups() {
+        let group = bracketed("(", list(unsigned::<u32>(), literal(",")), ")");
+        let groups = list(group, whitespace());
+        assert_eq!(
+            parse_all(groups, "(3) (1,3)"),
+            Ok(vec![vec![3], vec![1, 3]])
+        );
+    }
+
+    #[test]
+    fn parses_key_value_lines() {
+        let line = key_value(word(), ": ", list(word(), whitespace()));
+        assert_eq!(
+            parse_all(lines(line), "aaa: you hhh\r\nyou: bbb\n"),
+            Ok(vec![("aaa", vec!["you", "hhh"]), ("you", vec!["bbb"])])
+        );
+    }
+
+    #[test]
+    fn parses_sections() {
+        let range = unsigned::<u64>().skip(literal("-")).then(unsigned::<u64>());
+        let input = lines(range)
+            .skip(literal("\n\n"))
+            .then(lines(unsigned::<u64>()));
+        assert_eq!(
+            parse_all(input, "3-5\n10-14\n\n1\n5\n"),
+            Ok((vec![(3, 5), (10, 14)], vec![1, 5]))
+        );
+
+        let blocks = sections(lines(unsigned::<u32>()));
+        assert_eq!(
+            parse_all(blocks, "1\n2\n\n3\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn parses_optional_parts() {
+        let value = || unsigned::<u32>().then(optional(literal("!")));
+        assert_eq!(parse_all(value(), "1!"), Ok((1, Some("!"))));
+        assert_eq!(parse_all(value(), "1"), Ok((1, None)));
+    }
+
+    #[test]
+    fn reports_line_and_column() {
+        let groups = lines(bracketed("(", list(unsigned::<u32>(), literal(",")), ")"));
+        let error = parse_all(groups, "(1)\n(2,x)\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected `)`, found `,x)`"
+        );
+    }
+
+    #[test]
+    fn rejects_leftover_input() {
+        let error = parse_all(unsigned::<u32>(), "12 ab").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.expected, "end of input");
+    }
+}
@@ -0,0 +1,394 @@
+//! Line patterns like `"{name}: {outputs: ' '}"`, compiled to a regular expression.
+//! Used by [`line_parser!`](crate::line_parser) to generate a typed parser for one record per line.
+//!
+//! Pattern syntax:
+//! - `{field}` captures one or more characters into `field`.
+//! - `{field: 'sep'}` captures a list whose items are separated by `sep`.
+//! - `[...]` makes a part optional, its fields are `Option`s or empty lists.
+//! - `{{`, `}}`, `[[` and `]]` match a literal brace or bracket.
+//! - a space matches one or more spaces, any other character matches itself.
+use std::str::FromStr;
+
+use regex::Regex;
+
+use super::{Input, ParseError};
+
+struct Field {
+    name: String,
+    separator: Option<String>,
+}
+
+/// A compiled line pattern.
+pub struct Pattern {
+    source: &'static str,
+    regex: Regex,
+    fields: Vec<Field>,
+}
+
+impl Pattern {
+    /// Compiles a pattern and checks that it captures exactly the given fields.
+    pub fn new(source: &'static str, fields: &[&str]) -> Result<Self, String> {
+        let mut regex = String::from("^");
+        let mut captured: Vec<Field> = vec![];
+        let mut optional_depth = 0;
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' | '}' | '[' | ']' if chars.peek() == Some(&c) => {
+                    chars.next();
+                    regex.push_str(&regex::escape(&c.to_string()));
+                }
+                '{' => {
+                    let spec: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                    let field = parse_field(&spec)?;
+                    if captured.iter().any(|f| f.name == field.name) {
+                        return Err(format!("field `{}` is captured twice", field.name));
+                    }
+                    regex.push_str(&format!("(?P<{}>.+?)", field.name));
+                    captured.push(field);
+                }
+                '}' => return Err("unmatched `}`, use `}}` for a literal brace".into()),
+                '[' => {
+                    optional_depth += 1;
+                    regex.push_str("(?:");
+                }
+                ']' if optional_depth > 0 => {
+                    optional_depth -= 1;
+                    regex.push_str(")?");
+                }
+                ']' => return Err("unmatched `]`, use `]]` for a literal bracket".into()),
+                ' ' => {
+                    while chars.next_if_eq(&' ').is_some() {}
+                    regex.push_str(" +");
+                }
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+
+        if optional_depth > 0 {
+            return Err("unclosed `[`".into());
+        }
+
+        regex.push('$');
+
+        for name in fields {
+            if !captured.iter().any(|f| f.name == *name) {
+                return Err(format!("field `{name}` is not captured by the pattern"));
+            }
+        }
+        for field in &captured {
+            if !fields.contains(&field.name.as_str()) {
+                return Err(format!("`{{{}}}` is not a field of the struct", field.name));
+            }
+        }
+
+        Ok(Self {
+            source,
+            regex: Regex::new(&regex).map_err(|e| e.to_string())?,
+            fields: captured,
+        })
+    }
+
+    /// Matches a line, which starts at `input`.
+    pub fn captures<'a>(&'a self, input: Input<'a>) -> Result<Captures<'a>, ParseError> {
+        let line = input.rest().lines().next().unwrap_or_default();
+        match self.regex.captures(line) {
+            Some(captures) => Ok(Captures {
+                pattern: self,
+                captures,
+                input,
+            }),
+            None => Err(input.error(format!("a line matching `{}`", self.source))),
+        }
+    }
+}
+
+fn parse_field(spec: &str) -> Result<Field, String> {
+    let (name, separator) = match spec.split_once(':') {
+        Some((name, separator)) => {
+            let separator = separator
+                .trim()
+                .strip_prefix('\'')
+                .and_then(|s| s.strip_suffix('\''))
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| format!("expected a quoted separator in `{{{spec}}}`"))?;
+            (name, Some(separator.to_string()))
+        }
+        None => (spec, None),
+    };
+
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(format!("invalid field name in `{{{spec}}}`"));
+    }
+
+    Ok(Field {
+        name: name.into(),
+        separator,
+    })
+}
+
+/// The captured fields of a matched line.
+pub struct Captures<'a> {
+    pattern: &'a Pattern,
+    captures: regex::Captures<'a>,
+    input: Input<'a>,
+}
+
+impl Captures<'_> {
+    /// Converts the capture of a field, reporting the position of the capture on failure.
+    pub fn field<T: FromField>(&self, name: &str) -> Result<T, ParseError> {
+        let separator = self
+            .pattern
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .and_then(|f| f.separator.as_deref());
+
+        let capture = self.captures.name(name);
+        T::from_field(capture.map(|c| c.as_str()), separator).map_err(|expected| {
+            let position = match capture {
+                Some(capture) => self.input.take(capture.start()).1,
+                None => self.input,
+            };
+            position.error(format!("{expected} for `{name}`"))
+        })
+    }
+}
+
+/// Conversion from the text captured for a field, or `None` if an optional part did not match.
+/// Implemented for numbers, `char`, `bool`, `String`, lists and options. Other types can
+/// implement it with [`from_str_field`].
+pub trait FromField: Sized {
+    /// Returns a description of the expected value on failure, e.g. `a number`.
+    fn from_field(value: Option<&str>, separator: Option<&str>) -> Result<Self, String>;
+}
+
+/// Converts a required value with [`FromStr`], for implementations of [`FromField`].
+pub fn from_str_field<T: FromStr>(value: Option<&str>, expected: &str) -> Result<T, String> {
+    value
+        .ok_or_else(|| format!("{expected}, but the part is missing"))?
+        .trim()
+        .parse()
+        .map_err(|_| expected.to_string())
+}
+
+macro_rules! impl_from_field {
+    ($expected:literal: $($t:ty),*) => {
+        $(
+            impl FromField for $t {
+                fn from_field(value: Option<&str>, _: Option<&str>) -> Result<Self, String> {
+                    from_str_field(value, $expected)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_field!("a number": u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+impl_from_field!("a character": char);
+impl_from_field!("`true` or `false`": bool);
+impl_from_field!("a string": String);
+
+impl<T: FromField> FromField for Option<T> {
+    fn from_field(value: Option<&str>, separator: Option<&str>) -> Result<Self, String> {
+        match value {
+            Some(value) => T::from_field(Some(value), separator).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: FromField> FromField for Vec<T> {
+    fn from_field(value: Option<&str>, separator: Option<&str>) -> Result<Self, String> {
+        let Some(value) = value else {
+            return Ok(vec![]);
+        };
+
+        let items: Vec<&str> = match separator {
+            Some(separator) if !separator.trim().is_empty() => value.split(separator).collect(),
+            // whitespace separators also split on runs of whitespace.
+            _ => value.split_whitespace().collect(),
+        };
+
+        items
+            .into_iter()
+            .map(|item| T::from_field(Some(item), None).map_err(|e| format!("a list of {e}")))
+            .collect()
+    }
+}
+
+/// Declares a struct that is parsed from lines matching a pattern.
+/// See [`pattern`](crate::parse::pattern) for the pattern syntax.
+///
+/// ```
+/// advent_of_code::line_parser! {
+///     #[derive(Debug)]
+///     struct Device = "{name}: {outputs: ' '}" {
+///         name: String,
+///         outputs: Vec<String>,
+///     }
+/// }
+///
+/// let devices = Device::parse_lines("aaa: you hhh\nyou: bbb ccc\n").unwrap();
+/// assert_eq!(devices[1].outputs, vec!["bbb", "ccc"]);
+///
+/// let error = Device::parse_lines("aaa: you\nyou bbb").unwrap_err();
+/// assert_eq!((error.line, error.column), (2, 1));
+/// ```
+#[macro_export]
+macro_rules! line_parser {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = $pattern:literal {
+            $($field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field_vis $field: $ty),*
+        }
+
+        impl $name {
+            fn pattern() -> &'static $crate::parse::pattern::Pattern {
+                static PATTERN: std::sync::LazyLock<$crate::parse::pattern::Pattern> =
+                    std::sync::LazyLock::new(|| {
+                        $crate::parse::pattern::Pattern::new($pattern, &[$(stringify!($field)),*])
+                            .unwrap_or_else(|e| {
+                                panic!("invalid pattern for `{}`: {e}", stringify!($name))
+                            })
+                    });
+                &PATTERN
+            }
+
+            /// Parses a line that starts at `input`.
+            #[allow(dead_code)]
+            fn parse_at(input: $crate::parse::Input<'_>) -> Result<Self, $crate::parse::ParseError> {
+                let captures = Self::pattern().captures(input)?;
+                Ok(Self {
+                    $($field: captures.field(stringify!($field))?),*
+                })
+            }
+
+            /// Parses one record per line, skipping a trailing newline.
+            #[allow(dead_code)]
+            $vis fn parse_lines(input: &str) -> Result<Vec<Self>, $crate::parse::ParseError> {
+                $crate::parse::line_starts(input).map(Self::parse_at).collect()
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::parse::ParseError;
+
+            fn from_str(line: &str) -> Result<Self, Self::Err> {
+                Self::parse_at($crate::parse::Input::new(line))
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Pattern;
+
+    crate::line_parser! {
+        #[derive(Debug, PartialEq)]
+        struct Rotation = "{direction}{distance}" {
+            direction: char,
+            distance: u32,
+        }
+    }
+
+    crate::line_parser! {
+        #[derive(Debug, PartialEq)]
+        struct Machine = "[[{lights}]] {buttons: ' '}[ {{{joltage: ','}}}]" {
+            lights: String,
+            buttons: Vec<String>,
+            joltage: Vec<u32>,
+        }
+    }
+
+    crate::line_parser! {
+        #[derive(Debug, PartialEq)]
+        struct Point = "{x},{y}[,{z}]" {
+            x: i64,
+            y: i64,
+            z: Option<i64>,
+        }
+    }
+
+    #[test]
+    fn parses_fixed_shapes() {
+        assert_eq!(
+            "L68".parse(),
+            Ok(Rotation {
+                direction: 'L',
+                distance: 68
+            })
+        );
+        assert_eq!(
+            Point::parse_lines("1,-2\n3,4,5\n"),
+            Ok(vec![
+                Point {
+                    x: 1,
+                    y: -2,
+                    z: None
+                },
+                Point {
+                    x: 3,
+                    y: 4,
+                    z: Some(5)
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_lists_and_optional_parts() {
+        assert_eq!(
+            "[.##.] (3) (1,3) {3,5,4,7}".parse(),
+            Ok(Machine {
+                lights: ".##.".into(),
+                buttons: vec!["(3)".into(), "(1,3)".into()],
+                joltage: vec![3, 5, 4, 7],
+            })
+        );
+        assert_eq!("[#] (0)".parse::<Machine>().map(|m| m.joltage), Ok(vec![]));
+    }
+
+    #[test]
+    fn reports_mismatched_lines() {
+        let error = Point::parse_lines("1,2\n3;4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected a line matching `{x},{y}[,{z}]`, found `3;4`"
+        );
+    }
+
+    #[test]
+    fn reports_invalid_fields() {
+        let error = Point::parse_lines("1,2\n3,x\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number for `y`, found `x`"
+        );
+
+        let error = "[#] (0) {1,x}".parse::<Machine>().unwrap_err();
+        assert_eq!(error.expected, "a list of a number for `joltage`");
+        assert_eq!(error.column, 10);
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(Pattern::new("{a} {b}", &["a"]).is_err());
+        assert!(Pattern::new("{a}", &["a", "b"]).is_err());
+        assert!(Pattern::new("{a: }", &["a"]).is_err());
+        assert!(Pattern::new("[{a}", &["a"]).is_err());
+        assert!(Pattern::new("{a}}", &["a"]).is_err());
+        assert!(Pattern::new("{a} [{b}]", &["a", "b"]).is_ok());
+    }
+}
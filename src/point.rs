@@ -0,0 +1,191 @@
+//! Points in 3D space with integer coordinates.
+//!
+//! ```
+//! use advent_of_code::point::Point3;
+//!
+//! let a: Point3 = "162,817,812".parse().unwrap();
+//! let b = Point3::new(425, 690, 689);
+//! assert_eq!(a.distance_squared(b), 100427);
+//! assert_eq!(a.manhattan(b), 263 + 127 + 123);
+//! assert_eq!(b - a, Point3::new(263, -127, -123));
+//! ```
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+#[derive(PartialEq, Clone, Copy, Debug, Default, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The coordinate along an axis, where 0, 1 and 2 are x, y and z.
+    pub fn axis(&self, axis: usize) -> i64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("a 3D point has no axis {axis}"),
+        }
+    }
+
+    /// The squared Euclidean distance. It orders points like the Euclidean distance, without
+    /// floating point.
+    pub fn distance_squared(&self, other: Point3) -> i64 {
+        let d = *self - other;
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+
+    pub fn manhattan(&self, other: Point3) -> i64 {
+        let d = *self - other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+
+    /// The six points that share a face with this point.
+    pub fn neighbours(&self) -> [Point3; 6] {
+        [
+            Point3::new(1, 0, 0),
+            Point3::new(-1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(0, 0, -1),
+        ]
+        .map(|offset| *self + offset)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, factor: i64) -> Point3 {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsePointError(String);
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected a point like `1,2,3`, found `{}`.", self.0)
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+impl FromStr for Point3 {
+    type Err = ParsePointError;
+
+    /// Parses comma-separated coordinates, e.g. `1,-2,3` or `1, -2, 3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePointError(s.to_string());
+        let mut coordinates = s.split(',').map(|c| c.trim().parse::<i64>());
+        let mut next = || coordinates.next().ok_or_else(error)?.map_err(|_| error());
+        let point = Point3::new(next()?, next()?, next()?);
+        match coordinates.next() {
+            Some(_) => Err(error()),
+            None => Ok(point),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ParsePointError, Point3};
+
+    #[test]
+    fn parses_points() {
+        assert_eq!("1,-2,3".parse(), Ok(Point3::new(1, -2, 3)));
+        assert_eq!(" 1, 2, 3 ".parse(), Ok(Point3::new(1, 2, 3)));
+        assert_eq!(
+            "1,2".parse::<Point3>(),
+            Err(ParsePointError("1,2".to_string()))
+        );
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+        assert!("1,b,3".parse::<Point3>().is_err());
+        assert_eq!(Point3::new(1, -2, 3).to_string(), "1,-2,3");
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(-2, 6, 3);
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!(b.distance_squared(a), 25);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.manhattan(a), 0);
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let mut a = Point3::new(1, 2, 3);
+        assert_eq!(a + Point3::new(1, 1, 1), Point3::new(2, 3, 4));
+        assert_eq!(a * 3, Point3::new(3, 6, 9));
+        assert_eq!(-a, Point3::new(-1, -2, -3));
+        a += Point3::new(1, 0, 0);
+        a -= Point3::new(0, 0, 3);
+        assert_eq!(a, Point3::new(2, 2, 0));
+        assert!(a.neighbours().iter().all(|n| n.manhattan(a) == 1));
+    }
+}
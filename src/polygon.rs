@@ -0,0 +1,317 @@
+//! Rectilinear polygons on a grid of tiles.
+//!
+//! A [`RectilinearPolygon`] is a closed loop of [`Location`] vertices where every edge is
+//! horizontal or vertical. Tiles on the edges count as part of the polygon. Queries run on a
+//! compressed grid, so coordinates in the tens of thousands stay cheap.
+//!
+//! ```
+//! use advent_of_code::{Location, polygon::RectilinearPolygon};
+//!
+//! let square = RectilinearPolygon::new(vec![
+//!     Location { x: 0, y: 0 },
+//!     Location { x: 4, y: 0 },
+//!     Location { x: 4, y: 4 },
+//!     Location { x: 0, y: 4 },
+//! ])
+//! .unwrap();
+//! assert_eq!(square.area(), 16);
+//! assert_eq!(square.lattice_points(), 25);
+//! assert!(square.contains(&Location { x: 4, y: 2 }));
+//! assert!(!square.contains(&Location { x: 5, y: 2 }));
+//! ```
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use crate::{Location, compress::Compression};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolygonError {
+    TooFewVertices(usize),
+    /// The edge from the vertex at this index to the next one is diagonal.
+    DiagonalEdge(usize),
+}
+
+impl Display for PolygonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolygonError::TooFewVertices(count) => {
+                write!(f, "a polygon needs at least 4 vertices, got {count}.")
+            }
+            PolygonError::DiagonalEdge(index) => write!(
+                f,
+                "the edge from vertex {index} to the next vertex is not horizontal or vertical."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PolygonError {}
+
+#[derive(Clone, Debug)]
+pub struct RectilinearPolygon {
+    vertices: Vec<Location>,
+    /// Every tile of a compressed cell is either inside or outside the polygon.
+    compression: Compression,
+    /// Number of filled cells above and to the left of each cell, with an extra row and column.
+    filled: Vec<Vec<u32>>,
+}
+
+impl RectilinearPolygon {
+    /// Creates a polygon from its vertices in order. The last vertex connects to the first.
+    pub fn new(vertices: Vec<Location>) -> Result<Self, PolygonError> {
+        if vertices.len() < 4 {
+            return Err(PolygonError::TooFewVertices(vertices.len()));
+        }
+        if let Some(index) = vertices
+            .iter()
+            .circular_tuple_windows()
+            .position(|(a, b)| a.x != b.x && a.y != b.y)
+        {
+            return Err(PolygonError::DiagonalEdge(index));
+        }
+
+        let mut polygon = Self {
+            compression: Compression::new(&vertices),
+            vertices,
+            filled: vec![],
+        };
+        polygon.fill();
+        Ok(polygon)
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Location, Location)> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .circular_tuple_windows::<(_, _)>()
+    }
+
+    /// Marks the cells inside the polygon with a scanline per compressed row.
+    fn fill(&mut self) {
+        let (columns, rows) = (self.compression.xs.len(), self.compression.ys.len());
+        let mut filled = vec![vec![0; columns + 1]; rows + 1];
+
+        for row in 0..rows {
+            let y = *self.compression.ys.range(row).start();
+            let mut cells = vec![false; columns];
+
+            // vertical edges crossing the row, with the usual half-open rule at their ends.
+            let crossings = self
+                .edges()
+                .filter(|(a, b)| a.x == b.x && a.y.min(b.y) <= y && y < a.y.max(b.y))
+                .map(|(a, _)| a.x)
+                .sorted()
+                .collect_vec();
+            for (left, right) in crossings.into_iter().tuples() {
+                cells[self.column(left).unwrap()..=self.column(right).unwrap()].fill(true);
+            }
+
+            // tiles on the edges themselves.
+            for (a, b) in self.edges() {
+                if a.y.min(b.y) <= y && y <= a.y.max(b.y) {
+                    let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+                    cells[self.column(left).unwrap()..=self.column(right).unwrap()].fill(true);
+                }
+            }
+
+            for (column, cell) in cells.into_iter().enumerate() {
+                filled[row + 1][column + 1] = filled[row][column + 1] + filled[row + 1][column]
+                    - filled[row][column]
+                    + u32::from(cell);
+            }
+        }
+
+        self.filled = filled;
+    }
+
+    fn column(&self, x: u32) -> Option<usize> {
+        self.compression.xs.index(x)
+    }
+
+    fn row(&self, y: u32) -> Option<usize> {
+        self.compression.ys.index(y)
+    }
+
+    fn filled_count(&self, columns: (usize, usize), rows: (usize, usize)) -> u32 {
+        let (x1, x2) = (columns.0, columns.1 + 1);
+        let (y1, y2) = (rows.0, rows.1 + 1);
+        self.filled[y2][x2] + self.filled[y1][x1] - self.filled[y1][x2] - self.filled[y2][x1]
+    }
+
+    pub fn vertices(&self) -> &[Location] {
+        &self.vertices
+    }
+
+    /// The area enclosed by the loop through the vertices, using the shoelace formula.
+    pub fn area(&self) -> u64 {
+        let twice_area: i128 = self
+            .edges()
+            .map(|(a, b)| i128::from(a.x) * i128::from(b.y) - i128::from(b.x) * i128::from(a.y))
+            .sum();
+        (twice_area.unsigned_abs() / 2) as u64
+    }
+
+    /// The length of the loop, which is also the number of tiles on it.
+    pub fn perimeter(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| u64::from(a.x.abs_diff(b.x)) + u64::from(a.y.abs_diff(b.y)))
+            .sum()
+    }
+
+    pub fn boundary_points(&self) -> u64 {
+        self.perimeter()
+    }
+
+    /// The number of tiles strictly inside the loop, using Pick's theorem.
+    pub fn interior_points(&self) -> u64 {
+        self.area() + 1 - self.boundary_points() / 2
+    }
+
+    /// The number of tiles inside or on the loop.
+    pub fn lattice_points(&self) -> u64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Whether a tile is inside the polygon or on its edges.
+    pub fn contains(&self, location: &Location) -> bool {
+        self.contains_rectangle(location, location)
+    }
+
+    /// Whether every tile of the rectangle with corners `a` and `b` is inside the polygon or on
+    /// its edges.
+    pub fn contains_rectangle(&self, a: &Location, b: &Location) -> bool {
+        let (Some(x1), Some(x2), Some(y1), Some(y2)) = (
+            self.column(a.x.min(b.x)),
+            self.column(a.x.max(b.x)),
+            self.row(a.y.min(b.y)),
+            self.row(a.y.max(b.y)),
+        ) else {
+            return false;
+        };
+
+        let cells = (x2 - x1 + 1) * (y2 - y1 + 1);
+        self.filled_count((x1, x2), (y1, y2)) as usize == cells
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PolygonError, RectilinearPolygon};
+    use crate::Location;
+
+    #[cfg(test)]
+    fn polygon(vertices: &[(u32, u32)]) -> RectilinearPolygon {
+        RectilinearPolygon::new(
+            vertices
+                .iter()
+                .map(|(x, y)| Location { x: *x, y: *y })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[cfg(test)]
+    fn location(x: u32, y: u32) -> Location {
+        Location { x, y }
+    }
+
+    #[cfg(test)]
+    fn example() -> RectilinearPolygon {
+        polygon(&[
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ])
+    }
+
+    #[test]
+    fn measures_area() {
+        let polygon = example();
+        assert_eq!(polygon.area(), 30);
+        assert_eq!(polygon.perimeter(), 30);
+        assert_eq!(polygon.lattice_points(), 46);
+    }
+
+    #[test]
+    fn counts_lattice_points_like_a_flood_fill() {
+        let polygon = example();
+        let tiles = (0..14)
+            .flat_map(|x| (0..10).map(move |y| location(x, y)))
+            .filter(|l| polygon.contains(l))
+            .count();
+        assert_eq!(tiles as u64, polygon.lattice_points());
+        assert_eq!(polygon.interior_points(), 46 - 30);
+    }
+
+    #[test]
+    fn contains_points() {
+        let polygon = example();
+        assert!(polygon.contains(&location(7, 1)));
+        assert!(polygon.contains(&location(10, 6)));
+        assert!(polygon.contains(&location(2, 4)));
+        assert!(!polygon.contains(&location(3, 2)));
+        assert!(!polygon.contains(&location(8, 6)));
+        assert!(!polygon.contains(&location(12, 4)));
+        assert!(!polygon.contains(&location(0, 0)));
+    }
+
+    #[test]
+    fn contains_rectangles() {
+        let polygon = example();
+        assert!(polygon.contains_rectangle(&location(9, 5), &location(2, 3)));
+        assert!(polygon.contains_rectangle(&location(7, 1), &location(11, 5)));
+        assert!(!polygon.contains_rectangle(&location(2, 5), &location(11, 1)));
+        assert!(!polygon.contains_rectangle(&location(7, 3), &location(11, 7)));
+    }
+
+    #[test]
+    fn handles_large_coordinates() {
+        let polygon = polygon(&[
+            (10_000, 10_000),
+            (90_000, 10_000),
+            (90_000, 90_000),
+            (10_000, 90_000),
+        ]);
+        assert_eq!(polygon.area(), 80_000 * 80_000);
+        assert!(polygon.contains_rectangle(&location(10_000, 10_000), &location(90_000, 90_000)));
+        assert!(!polygon.contains_rectangle(&location(10_000, 10_000), &location(90_001, 90_000)));
+    }
+
+    #[test]
+    fn handles_the_largest_coordinates() {
+        let polygon = polygon(&[
+            (0, u32::MAX - 2),
+            (u32::MAX, u32::MAX - 2),
+            (u32::MAX, u32::MAX),
+            (0, u32::MAX),
+        ]);
+        assert_eq!(polygon.area(), u64::from(u32::MAX) * 2);
+        assert_eq!(polygon.lattice_points(), (u64::from(u32::MAX) + 1) * 3);
+        assert!(polygon.contains(&location(u32::MAX, u32::MAX)));
+        assert!(
+            polygon.contains_rectangle(&location(0, u32::MAX - 2), &location(u32::MAX, u32::MAX))
+        );
+        assert!(!polygon.contains(&location(u32::MAX, u32::MAX - 3)));
+    }
+
+    #[test]
+    fn rejects_invalid_polygons() {
+        let vertices = |v: &[(u32, u32)]| v.iter().map(|(x, y)| location(*x, *y)).collect();
+        assert_eq!(
+            RectilinearPolygon::new(vertices(&[(0, 0), (1, 0), (1, 1)])).unwrap_err(),
+            PolygonError::TooFewVertices(3)
+        );
+        assert_eq!(
+            RectilinearPolygon::new(vertices(&[(0, 0), (2, 0), (2, 2), (1, 1)])).unwrap_err(),
+            PolygonError::DiagonalEdge(2)
+        );
+    }
+}
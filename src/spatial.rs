@@ -0,0 +1,227 @@
+//! Nearest-neighbour queries over 3D points.
+//!
+//! [`KdTree`] answers k-nearest queries, and [`KdTree::closest_pairs`] yields all pairs of points
+//! by increasing distance, only computing as many neighbours as the pairs that are taken.
+//!
+//! ```
+//! use advent_of_code::{point::Point3, spatial::KdTree};
+//!
+//! let points = [Point3::new(0, 0, 0), Point3::new(10, 0, 0), Point3::new(1, 1, 0)];
+//! let tree = KdTree::new(&points);
+//! let pairs: Vec<_> = tree.closest_pairs().take(2).collect();
+//! assert_eq!(pairs, vec![(0, 2, 2), (1, 2, 82)]);
+//! ```
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use crate::point::Point3;
+
+/// A k-d tree over a slice of points. Points are referred to by their index in the slice.
+#[derive(Clone, Debug)]
+pub struct KdTree<'a> {
+    points: &'a [Point3],
+    /// Point indices in tree order: the median of each range is the node splitting that range.
+    nodes: Vec<usize>,
+}
+
+impl<'a> KdTree<'a> {
+    pub fn new(points: &'a [Point3]) -> Self {
+        let mut nodes: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut nodes, 0);
+        Self { points, nodes }
+    }
+
+    fn build(points: &[Point3], nodes: &mut [usize], depth: usize) {
+        if nodes.len() <= 1 {
+            return;
+        }
+        let axis = depth % 3;
+        let median = nodes.len() / 2;
+        nodes.select_nth_unstable_by_key(median, |index| points[*index].axis(axis));
+        let (left, right) = nodes.split_at_mut(median);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    pub fn points(&self) -> &'a [Point3] {
+        self.points
+    }
+
+    /// The `k` points closest to `target` as `(squared distance, index)`, closest first.
+    /// Ties are broken by index. The point with index `exclude` is skipped.
+    pub fn nearest(&self, target: Point3, k: usize, exclude: Option<usize>) -> Vec<(i64, usize)> {
+        let mut best: BinaryHeap<(i64, usize)> = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(&self.nodes, 0, target, k, exclude, &mut best);
+        }
+        best.into_sorted_vec()
+    }
+
+    fn search(
+        &self,
+        nodes: &[usize],
+        depth: usize,
+        target: Point3,
+        k: usize,
+        exclude: Option<usize>,
+        best: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        let median = nodes.len() / 2;
+        let index = nodes[median];
+        let point = self.points[index];
+
+        if exclude != Some(index) {
+            best.push((point.distance_squared(target), index));
+            if best.len() > k {
+                best.pop();
+            }
+        }
+
+        let axis = depth % 3;
+        let offset = target.axis(axis) - point.axis(axis);
+        let (near, far) = if offset < 0 {
+            (&nodes[..median], &nodes[median + 1..])
+        } else {
+            (&nodes[median + 1..], &nodes[..median])
+        };
+
+        self.search(near, depth + 1, target, k, exclude, best);
+        // the far side can only hold closer points if the splitting plane is close enough.
+        if best.len() < k || offset * offset <= best.peek().unwrap().0 {
+            self.search(far, depth + 1, target, k, exclude, best);
+        }
+    }
+
+    /// All pairs of points `(i, j, squared distance)` with `i < j`, closest first.
+    /// Ties are ordered by `i`, then `j`.
+    pub fn closest_pairs(&self) -> ClosestPairs<'_, 'a> {
+        let mut pairs = ClosestPairs {
+            tree: self,
+            neighbours: vec![NeighbourQueue::default(); self.points.len()],
+            heap: BinaryHeap::new(),
+        };
+        for index in 0..self.points.len() {
+            pairs.push_next(index);
+        }
+        pairs
+    }
+}
+
+/// The neighbours of a point that have not been paired yet.
+#[derive(Clone, Debug, Default)]
+struct NeighbourQueue {
+    /// Neighbours with a higher index, closest first.
+    pending: VecDeque<(i64, usize)>,
+    /// How many nearest neighbours have been queried so far.
+    queried: usize,
+}
+
+/// Iterator over the pairs of points of a [`KdTree`] by increasing distance.
+///
+/// Every point lazily queries its nearest neighbours, doubling the number of neighbours whenever
+/// it runs out, and a heap merges the next pair of every point.
+pub struct ClosestPairs<'t, 'a> {
+    tree: &'t KdTree<'a>,
+    neighbours: Vec<NeighbourQueue>,
+    heap: BinaryHeap<Reverse<(i64, usize, usize)>>,
+}
+
+impl ClosestPairs<'_, '_> {
+    /// Pushes the next pair of a point onto the heap, querying more neighbours if needed.
+    fn push_next(&mut self, index: usize) {
+        let count = self.tree.points.len();
+        let queue = &mut self.neighbours[index];
+
+        while queue.pending.is_empty() && queue.queried < count - 1 {
+            let k = (queue.queried * 2).max(8).min(count - 1);
+            let target = self.tree.points[index];
+            queue.pending = self
+                .tree
+                .nearest(target, k, Some(index))
+                .into_iter()
+                .skip(queue.queried)
+                .filter(|(_, other)| *other > index)
+                .collect();
+            queue.queried = k;
+        }
+
+        if let Some((distance, other)) = queue.pending.pop_front() {
+            self.heap.push(Reverse((distance, index, other)));
+        }
+    }
+}
+
+impl Iterator for ClosestPairs<'_, '_> {
+    type Item = (usize, usize, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((distance, a, b)) = self.heap.pop()?;
+        self.push_next(a);
+        Some((a, b, distance))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::KdTree;
+    use crate::point::Point3;
+    use itertools::Itertools;
+
+    #[cfg(test)]
+    fn scattered_points(count: i64) -> Vec<Point3> {
+        // a small linear congruential generator, so the test needs no dependencies.
+        let mut state: i64 = 42;
+        let mut next = move || {
+            state = (state * 1103515245 + 12345) % (1 << 31);
+            state % 1000
+        };
+        (0..count)
+            .map(|_| Point3::new(next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_nearest_points() {
+        let points = scattered_points(200);
+        let tree = KdTree::new(&points);
+        let target = Point3::new(500, 500, 500);
+
+        let expected = (0..points.len())
+            .map(|i| (points[i].distance_squared(target), i))
+            .sorted()
+            .take(10)
+            .collect_vec();
+        assert_eq!(tree.nearest(target, 10, None), expected);
+        assert_eq!(tree.nearest(points[3], 1, None), vec![(0, 3)]);
+        assert_ne!(tree.nearest(points[3], 1, Some(3))[0].1, 3);
+    }
+
+    #[test]
+    fn yields_all_pairs_in_order() {
+        let points = scattered_points(60);
+        let tree = KdTree::new(&points);
+
+        let expected = (0..points.len())
+            .tuple_combinations()
+            .map(|(a, b)| (points[a].distance_squared(points[b]), a, b))
+            .sorted()
+            .map(|(distance, a, b)| (a, b, distance))
+            .collect_vec();
+        assert_eq!(tree.closest_pairs().collect_vec(), expected);
+    }
+
+    #[test]
+    fn handles_tiny_inputs() {
+        assert_eq!(KdTree::new(&[]).closest_pairs().count(), 0);
+        assert_eq!(KdTree::new(&[Point3::ORIGIN]).closest_pairs().count(), 0);
+        assert!(KdTree::new(&[]).nearest(Point3::ORIGIN, 3, None).is_empty());
+    }
+}
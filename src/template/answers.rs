@@ -0,0 +1,586 @@
+/// Module that keeps a per-day book of submitted answers and the verdicts of the server.
+/// The book is consulted before submitting, so known-wrong answers never reach the network.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, config, rate_limit};
+
+/// The verdict of the server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The answer was submitted during a cooldown and was not checked.
+    TooSoon,
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Derives the verdict from the response message of a submission.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if response.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else if response.contains("That's not the right answer") {
+            if response.contains("too high") {
+                Verdict::TooHigh
+            } else if response.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Returns `true` if the server checked the answer and rejected it.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::TooSoon => "too_soon",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "too_soon" => Ok(Verdict::TooSoon),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    AlreadyAccepted(String),
+    KnownWrong(Verdict),
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyAccepted(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Rejection::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Rejection::NotBelow(bound) => {
+                write!(f, "`{bound}` was too high, so the answer has to be lower.")
+            }
+            Rejection::NotAbove(bound) => {
+                write!(f, "`{bound}` was too low, so the answer has to be higher.")
+            }
+        }
+    }
+}
+
+/// The submissions of a single day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug)]
+pub struct AnswerBook {
+    pub day: Day,
+    pub submissions: Vec<Submission>,
+    /// Seconds since the unix epoch until which the server refuses new submissions.
+    pub locked_until: Option<u64>,
+}
+
+impl AnswerBook {
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            submissions: vec![],
+            locked_until: None,
+        }
+    }
+
+    /// Rehydrate the book of a day from its JSON file. If not present, returns an empty book.
+    pub fn read_from_file(day: Day) -> Result<Self, String> {
+        match fs::read_to_string(config().answers_path(day)) {
+            Ok(s) => AnswerBook::try_from(s),
+            Err(_) => Ok(AnswerBook::new(day)),
+        }
+    }
+
+    /// Reads the book of a day and adds the answers shown as accepted on the downloaded puzzle
+    /// description, so days solved without this tool can be verified too.
+    pub fn read_with_puzzle(day: Day) -> Result<Self, String> {
+        let mut book = Self::read_from_file(day)?;
+        let Ok(puzzle) = fs::read_to_string(config().puzzle_path(day)) else {
+            return Ok(book);
+        };
+
+        if book.accept_known(&puzzle_answers(&puzzle))
+            && let Err(e) = book.store_file()
+        {
+            eprintln!("failed to store answer book: {e}");
+        }
+        Ok(book)
+    }
+
+    /// Records answers known to be correct, in part order, for parts without an accepted
+    /// answer. Returns `true` if any answer was added.
+    pub fn accept_known(&mut self, answers: &[String]) -> bool {
+        let mut added = false;
+        for (part, answer) in (1..=2).zip(answers) {
+            if self.accepted(part).is_none() {
+                self.record(part, answer, Verdict::Correct);
+                added = true;
+            }
+        }
+        added
+    }
+
+    /// Dehydrate the book to its JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = config().answers_path(self.day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Returns the accepted answer of a part, if any.
+    pub fn accepted(&self, part: u8) -> Option<&str> {
+        self.submissions
+            .iter()
+            .find(|s| s.part == part && s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Checks an answer against previous submissions of the same part.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Rejection> {
+        if let Some(accepted) = self.accepted(part) {
+            return Err(Rejection::AlreadyAccepted(accepted.into()));
+        }
+
+        let previous = self.submissions.iter().filter(|s| s.part == part);
+
+        if let Some(known) = previous
+            .clone()
+            .find(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Err(Rejection::KnownWrong(known.verdict));
+        }
+
+        // bounds only apply to numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            previous
+                .clone()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, s.answer.as_str())))
+        };
+
+        if let Some((_, lowest)) = bound(Verdict::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min_by_key(|(high, _)| *high)
+        {
+            return Err(Rejection::NotBelow(lowest.into()));
+        }
+
+        if let Some((_, highest)) = bound(Verdict::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max_by_key(|(low, _)| *low)
+        {
+            return Err(Rejection::NotAbove(highest.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Records a submitted answer with the verdict of the server.
+    pub fn record(&mut self, part: u8, answer: &str, verdict: Verdict) {
+        self.submissions.push(Submission {
+            part,
+            answer: answer.into(),
+            verdict,
+            submitted_at: rate_limit::now(),
+        });
+    }
+
+    /// Stores that the server refuses new submissions for `cooldown`.
+    pub fn lock_for(&mut self, cooldown: Duration) {
+        self.locked_until = Some(rate_limit::now() + cooldown.as_secs());
+    }
+
+    /// Returns the time until the server accepts new submissions, if a cooldown is active.
+    pub fn remaining_cooldown(&self) -> Option<Duration> {
+        let remaining = self.locked_until?.checked_sub(rate_limit::now())?;
+        (remaining > 0).then(|| Duration::from_secs(remaining))
+    }
+}
+
+/// Returns the accepted answers a puzzle description shows (`Your puzzle answer was ...`), in
+/// part order.
+pub fn puzzle_answers(puzzle: &str) -> Vec<String> {
+    puzzle
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Your puzzle answer was"))
+        .map(|answer| {
+            answer
+                .trim()
+                .trim_end_matches('.')
+                .trim_matches('`')
+                .to_string()
+        })
+        .filter(|answer| !answer.is_empty())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<AnswerBook> for JsonValue {
+    fn from(value: AnswerBook) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.iter().map(JsonValue::from).collect()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "locked_until".into(),
+            match value.locked_until {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for AnswerBook {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("expected `json.day` to be a Day struct.")?;
+
+        let submissions = json
+            .get("submissions")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.submissions` to be an array.")?;
+
+        // NOTE: books written before cooldowns were tracked do not have this key.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let locked_until = json
+            .get("locked_until")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        Ok(AnswerBook {
+            day,
+            submissions: submissions
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+            locked_until,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "submitted_at".into(),
+            JsonValue::Number(value.submitted_at as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let submitted_at = json
+            .get("submitted_at")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.submitted_at to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+            submitted_at: *submitted_at as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerBook, Rejection, Verdict};
+    use crate::day;
+
+    fn get_mock_book() -> AnswerBook {
+        let mut book = AnswerBook::new(day!(1));
+        book.record(1, "500", Verdict::TooHigh);
+        book.record(1, "100", Verdict::TooLow);
+        book.record(1, "300", Verdict::Incorrect);
+        book.record(1, "700", Verdict::TooHigh);
+        book.record(1, "50", Verdict::TooLow);
+        book
+    }
+
+    mod verdicts {
+        use super::Verdict;
+
+        #[test]
+        fn parses_responses() {
+            assert_eq!(
+                Verdict::from_response("That's the right answer! You are one gold star closer."),
+                Verdict::Correct
+            );
+            assert_eq!(
+                Verdict::from_response(
+                    "That's not the right answer; your answer is too high. If you're stuck..."
+                ),
+                Verdict::TooHigh
+            );
+            assert_eq!(
+                Verdict::from_response("That's not the right answer; your answer is too low."),
+                Verdict::TooLow
+            );
+            assert_eq!(
+                Verdict::from_response("That's not the right answer. If you're stuck..."),
+                Verdict::Incorrect
+            );
+            assert_eq!(
+                Verdict::from_response("You gave an answer too recently; you have to wait..."),
+                Verdict::TooSoon
+            );
+            assert_eq!(
+                Verdict::from_response("You don't seem to be solving the right level."),
+                Verdict::WrongLevel
+            );
+        }
+    }
+
+    mod check {
+        use super::{AnswerBook, Rejection, Verdict, get_mock_book};
+        use crate::day;
+
+        #[test]
+        fn allows_answers_within_bounds() {
+            let book = get_mock_book();
+            assert_eq!(book.check(1, "101"), Ok(()));
+            assert_eq!(book.check(1, "499"), Ok(()));
+            assert_eq!(book.check(1, "abc"), Ok(()));
+            assert_eq!(book.check(2, "500"), Ok(()));
+        }
+
+        #[test]
+        fn refuses_known_wrong_answers() {
+            let book = get_mock_book();
+            assert_eq!(
+                book.check(1, "300"),
+                Err(Rejection::KnownWrong(Verdict::Incorrect))
+            );
+            assert_eq!(
+                book.check(1, "700"),
+                Err(Rejection::KnownWrong(Verdict::TooHigh))
+            );
+        }
+
+        #[test]
+        fn refuses_answers_out_of_bounds() {
+            let book = get_mock_book();
+            assert_eq!(book.check(1, "600"), Err(Rejection::NotBelow("500".into())));
+            assert_eq!(book.check(1, "75"), Err(Rejection::NotAbove("100".into())));
+        }
+
+        #[test]
+        fn refuses_solved_parts() {
+            let mut book = AnswerBook::new(day!(1));
+            book.record(1, "42", Verdict::Correct);
+            assert_eq!(
+                book.check(1, "43"),
+                Err(Rejection::AlreadyAccepted("42".into()))
+            );
+        }
+
+        #[test]
+        fn ignores_unchecked_submissions() {
+            let mut book = AnswerBook::new(day!(1));
+            book.record(1, "42", Verdict::TooSoon);
+            assert_eq!(book.check(1, "42"), Ok(()));
+        }
+    }
+
+    mod seeding {
+        use super::{AnswerBook, Verdict};
+        use crate::{day, template::answers::puzzle_answers};
+
+        #[test]
+        fn reads_answers_from_puzzles() {
+            let puzzle = "## --- Day 1 ---\n\nYour puzzle answer was `123`.\n\n## --- Part Two ---\n\nYour puzzle answer was `abc`.\n";
+            assert_eq!(puzzle_answers(puzzle), vec!["123", "abc"]);
+            assert!(puzzle_answers("## --- Day 1 ---\n").is_empty());
+        }
+
+        #[test]
+        fn keeps_accepted_answers() {
+            let mut book = AnswerBook::new(day!(1));
+            book.record(1, "42", Verdict::Correct);
+            book.record(2, "7", Verdict::TooLow);
+
+            assert!(book.accept_known(&["41".into(), "8".into()]));
+            assert_eq!(book.accepted(1), Some("42"));
+            assert_eq!(book.accepted(2), Some("8"));
+            assert!(!book.accept_known(&["41".into(), "8".into()]));
+        }
+    }
+
+    mod cooldown {
+        use std::time::Duration;
+
+        use super::AnswerBook;
+        use crate::day;
+
+        #[test]
+        fn tracks_remaining_cooldown() {
+            let mut book = AnswerBook::new(day!(1));
+            assert_eq!(book.remaining_cooldown(), None);
+
+            book.lock_for(Duration::from_secs(60));
+            let remaining = book.remaining_cooldown().unwrap();
+            assert!(remaining > Duration::from_secs(58) && remaining <= Duration::from_secs(60));
+        }
+
+        #[test]
+        fn expires_cooldown() {
+            let mut book = AnswerBook::new(day!(1));
+            book.locked_until = Some(1);
+            assert_eq!(book.remaining_cooldown(), None);
+        }
+    }
+
+    mod serialization {
+        use super::{AnswerBook, get_mock_book};
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn round_trips_books() {
+            let mut book = get_mock_book();
+            book.locked_until = Some(1_733_000_000);
+            let json = JsonValue::from(book.clone()).stringify().unwrap();
+            let parsed = AnswerBook::try_from(json).unwrap();
+            assert_eq!(parsed.day, book.day);
+            assert_eq!(parsed.submissions, book.submissions);
+            assert_eq!(parsed.locked_until, book.locked_until);
+        }
+
+        #[test]
+        fn handles_books_without_cooldown() {
+            let json = r#"{ "day": "01", "submissions": [] }"#.to_string();
+            let parsed = AnswerBook::try_from(json).unwrap();
+            assert_eq!(parsed.locked_until, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_books() {
+            let json = r#"{ "day": "01", "submissions": [{ "part": 3 }] }"#.to_string();
+            AnswerBook::try_from(json).unwrap();
+        }
+    }
+}
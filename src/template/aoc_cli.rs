@@ -1,16 +1,20 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
-    process::{Command, Output, Stdio},
+    io::Read,
+    process::{Child, Command, ExitStatus, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
 };
 
-use crate::template::Day;
+use crate::template::{Day, config, get_year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    TimedOut(Duration),
 }
 
 impl Display for AocCommandError {
@@ -18,8 +22,17 @@ impl Display for AocCommandError {
         match self {
             AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::BadExitStatus(output) => {
+                write!(f, "aoc-cli exited with {}.", output.status)?;
+                // captured output is not visible otherwise, keep it as the error detail.
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                if !stdout.trim().is_empty() {
+                    write!(f, "\n{}", stdout.trim_end())?;
+                }
+                Ok(())
+            }
+            AocCommandError::TimedOut(timeout) => {
+                write!(f, "aoc-cli did not finish within {timeout:?}.")
             }
         }
     }
@@ -33,22 +46,6 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
-}
-
 pub fn download(day: Day) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
@@ -65,34 +62,28 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
+    let output = call_aoc_cli(&args, false)?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
 
+/// Submits an answer, capturing the response printed by aoc-cli in `stdout` of the returned [`Output`].
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    call_aoc_cli(&args, true)
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    config().input_path(day).display().to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+    config().puzzle_path(day).display().to_string()
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -108,18 +99,75 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String], capture_stdout: bool) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
+    let mut cmd = Command::new("aoc");
+    cmd.args(args)
+        .stdout(if capture_stdout {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        })
+        .stderr(Stdio::inherit());
+
+    // aoc-cli reads the session token from this variable before falling back to its own session file.
+    if let Some(token) = config().session_token() {
+        cmd.env("ADVENT_OF_CODE_SESSION", token);
+    }
+
+    let mut child = cmd
+        .spawn()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    // read captured output on a separate thread, so a full pipe cannot block the child.
+    let reader = child.stdout.take().map(|mut stdout| {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            let _ = stdout.read_to_end(&mut buf);
+            buf
+        })
+    });
+
+    let status = wait_with_timeout(child, config().timeouts.aoc)?;
+
+    // NOTE: stderr is always inherited, so there is nothing to capture.
+    let output = Output {
+        status,
+        stdout: reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default(),
+        stderr: vec![],
+    };
+
     if output.status.success() {
         Ok(output)
     } else {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+fn wait_with_timeout(
+    mut child: Child,
+    timeout: Option<Duration>,
+) -> Result<ExitStatus, AocCommandError> {
+    let Some(timeout) = timeout else {
+        return child
+            .wait()
+            .map_err(|_| AocCommandError::CommandNotCallable);
+    };
+
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AocCommandError::TimedOut(timeout));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(_) => return Err(AocCommandError::CommandNotCallable),
+        }
+    }
+}
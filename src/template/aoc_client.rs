@@ -0,0 +1,105 @@
+/// Abstraction over the ways of talking to adventofcode.com.
+/// The backend is selected with `client.backend` in `aoc.toml`.
+use std::{fmt::Display, io};
+
+use crate::template::{
+    Day, aoc_cli,
+    aoc_web::AocWeb,
+    config::{Backend, config},
+    rate_limit::throttle,
+};
+
+/// Operations the template needs from adventofcode.com.
+pub trait AocClient {
+    /// Downloads the input and the puzzle description of a day into the data directory.
+    fn download(&self, day: Day) -> Result<(), AocClientError>;
+
+    /// Submits an answer and returns the response message.
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError>;
+}
+
+#[derive(Debug)]
+pub enum AocClientError {
+    Cli(aoc_cli::AocCommandError),
+    MissingYear,
+    MissingSession,
+    Http(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::Cli(aoc_cli::AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            AocClientError::Cli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            AocClientError::MissingYear => {
+                write!(f, "no event year configured, set `year` in aoc.toml.")
+            }
+            AocClientError::MissingSession => write!(
+                f,
+                "no session token found, set `{}` or configure `session.file` in aoc.toml.",
+                config().session.env
+            ),
+            AocClientError::Http(400) => write!(
+                f,
+                "adventofcode.com rejected the request (400), the session token might be expired."
+            ),
+            AocClientError::Http(404) => write!(
+                f,
+                "adventofcode.com could not find the page (404), the puzzle might not be unlocked yet."
+            ),
+            AocClientError::Http(status) => {
+                write!(f, "adventofcode.com responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<aoc_cli::AocCommandError> for AocClientError {
+    fn from(e: aoc_cli::AocCommandError) -> Self {
+        AocClientError::Cli(e)
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Backend that shells out to the `aoc` binary of [aoc-cli](https://github.com/scarvalhojr/aoc-cli).
+pub struct AocCli;
+
+impl AocClient for AocCli {
+    fn download(&self, day: Day) -> Result<(), AocClientError> {
+        aoc_cli::check()?;
+        throttle(config().client.request_interval);
+        aoc_cli::download(day)?;
+        Ok(())
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        aoc_cli::check()?;
+        throttle(config().client.request_interval);
+        let output = aoc_cli::submit(day, part, answer)?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Returns the client for the configured backend.
+pub fn client() -> Box<dyn AocClient> {
+    match config().client.backend {
+        Backend::Native => Box::new(AocWeb::from_config()),
+        Backend::AocCli => Box::new(AocCli),
+    }
+}
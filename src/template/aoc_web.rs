@@ -0,0 +1,325 @@
+/// Built-in client for adventofcode.com, authenticated with the session cookie.
+use std::{fs, path::Path, time::Duration};
+
+use ureq::Agent;
+
+use crate::template::{
+    Day,
+    aoc_client::{AocClient, AocClientError},
+    config, html, rate_limit,
+};
+
+/// Identifies the template to the Advent of Code servers, as requested by their automation
+/// guidelines: the `repository` of `Cargo.toml` and the `client.contact` of `aoc.toml`.
+fn user_agent(contact: Option<&str>) -> String {
+    let about = [env!("CARGO_PKG_REPOSITORY"), contact.unwrap_or_default()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+
+    match about.is_empty() {
+        true => format!("advent_of_code/{}", env!("CARGO_PKG_VERSION")),
+        false => format!(
+            "advent_of_code/{} ({})",
+            env!("CARGO_PKG_VERSION"),
+            about.join("; ")
+        ),
+    }
+}
+
+pub struct AocWeb {
+    base_url: String,
+    year: Option<u16>,
+    session: Option<String>,
+    request_interval: Duration,
+    agent: Agent,
+}
+
+impl AocWeb {
+    pub fn new(
+        base_url: &str,
+        year: Option<u16>,
+        session: Option<String>,
+        timeout: Option<Duration>,
+        request_interval: Duration,
+        contact: Option<&str>,
+    ) -> Self {
+        let agent = Agent::config_builder()
+            .timeout_global(timeout)
+            .http_status_as_error(false)
+            .user_agent(user_agent(contact))
+            .build()
+            .into();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            year,
+            session,
+            request_interval,
+            agent,
+        }
+    }
+
+    pub fn from_config() -> Self {
+        let config = config();
+        Self::new(
+            &config.client.base_url,
+            config.year,
+            config.session_token(),
+            config.timeouts.aoc,
+            config.client.request_interval,
+            config.client.contact.as_deref(),
+        )
+    }
+
+    fn day_url(&self, day: Day) -> Result<String, AocClientError> {
+        let year = self.year.ok_or(AocClientError::MissingYear)?;
+        Ok(format!("{}/{year}/day/{}", self.base_url, day.into_inner()))
+    }
+
+    fn cookie(&self) -> Result<String, AocClientError> {
+        self.session
+            .as_ref()
+            .map(|session| format!("session={session}"))
+            .ok_or(AocClientError::MissingSession)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let cookie = self.cookie()?;
+        rate_limit::throttle(self.request_interval);
+
+        let response = self
+            .agent
+            .get(url)
+            .header("Cookie", cookie)
+            .call()
+            .map_err(|e| AocClientError::Transport(e.to_string()))?;
+
+        read_body(response)
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)?))
+    }
+
+    /// Fetches the puzzle description of a day, converted to Markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let page = self.get(&self.day_url(day)?)?;
+        Ok(html::puzzle_to_markdown(&page, &self.base_url))
+    }
+}
+
+fn read_body(mut response: ureq::http::Response<ureq::Body>) -> Result<String, AocClientError> {
+    let status = response.status().as_u16();
+    if !(200..300).contains(&status) {
+        return Err(AocClientError::Http(status));
+    }
+
+    response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| AocClientError::Transport(e.to_string()))
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+impl AocClient for AocWeb {
+    fn download(&self, day: Day) -> Result<(), AocClientError> {
+        let input_path = config().input_path(day);
+        let puzzle_path = config().puzzle_path(day);
+
+        let input = self.input(day)?;
+        let puzzle = self.puzzle(day)?;
+
+        write_file(&input_path, &input)?;
+        write_file(&puzzle_path, &puzzle)?;
+
+        println!("🎄 Successfully wrote input to {input_path:?}.");
+        println!("🎄 Successfully wrote puzzle to {puzzle_path:?}.");
+        Ok(())
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day)?);
+        let cookie = self.cookie()?;
+        rate_limit::throttle(self.request_interval);
+
+        let response = self
+            .agent
+            .post(url)
+            .header("Cookie", cookie)
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])
+            .map_err(|e| AocClientError::Transport(e.to_string()))?;
+
+        let page = read_body(response)?;
+        Ok(html::response_to_markdown(&page, &self.base_url))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use super::AocWeb;
+    use crate::{
+        day,
+        template::aoc_client::{AocClient, AocClientError},
+    };
+
+    /// A request as seen by the mock server.
+    struct Recorded {
+        request_line: String,
+        headers: Vec<String>,
+        body: String,
+    }
+
+    /// Serves a single HTTP request with the given status and body on a random local port.
+    fn mock_server(status: u16, response: &'static str) -> (String, JoinHandle<Recorded>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut headers = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_string();
+                if line.is_empty() {
+                    break;
+                }
+                headers.push(line);
+            }
+
+            let content_length = headers
+                .iter()
+                .find_map(|h| {
+                    h.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+
+            Recorded {
+                request_line: request_line.trim_end().into(),
+                headers,
+                body: String::from_utf8(body).unwrap(),
+            }
+        });
+
+        (url, handle)
+    }
+
+    fn client(url: &str) -> AocWeb {
+        AocWeb::new(
+            url,
+            Some(2025),
+            Some("abc".into()),
+            None,
+            Duration::ZERO,
+            Some("me@example.com"),
+        )
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, server) = mock_server(200, "1,2,3\n");
+        let input = client(&url).input(day!(1)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1,2,3\n");
+        assert_eq!(request.request_line, "GET /2025/day/1/input HTTP/1.1");
+        assert!(
+            request
+                .headers
+                .iter()
+                .any(|h| h.eq_ignore_ascii_case("cookie: session=abc"))
+        );
+        assert!(request.headers.iter().any(|h| {
+            h.to_ascii_lowercase()
+                .starts_with("user-agent: advent_of_code/")
+                && h.ends_with("me@example.com)")
+        }));
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, server) = mock_server(
+            200,
+            r#"<main><article class="day-desc"><h2>--- Day 3: Test ---</h2><p>Hello <em>world</em>.</p></article></main>"#,
+        );
+        let puzzle = client(&url).puzzle(day!(3)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(puzzle, "## --- Day 3: Test ---\n\nHello *world*.\n");
+        assert_eq!(request.request_line, "GET /2025/day/3 HTTP/1.1");
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = mock_server(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let response = client(&url).submit(day!(12), 2, "4711").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(response, "That's the right answer!");
+        assert_eq!(request.request_line, "POST /2025/day/12/answer HTTP/1.1");
+        assert_eq!(request.body, "level=2&answer=4711");
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (url, server) = mock_server(404, "Not found");
+        let result = client(&url).input(day!(1));
+        server.join().unwrap();
+
+        assert!(matches!(result, Err(AocClientError::Http(404))));
+    }
+
+    #[test]
+    fn requires_session() {
+        let client = AocWeb::new(
+            "http://127.0.0.1:1",
+            Some(2025),
+            None,
+            None,
+            Duration::ZERO,
+            None,
+        );
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::MissingSession)
+        ));
+    }
+}
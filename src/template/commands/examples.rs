@@ -0,0 +1,118 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, config,
+    examples::{CodeBlock, code_blocks, expected_answers, fill_expected_answer},
+};
+
+const PREVIEW_LINES: usize = 5;
+
+/// Lists the code blocks of a downloaded puzzle description, or writes the picked ones to example files.
+/// The first picked block becomes `NN.txt`, any further block `k` becomes `NN-k.txt`.
+pub fn handle(day: Day, pick: Option<Vec<usize>>, write_tests: bool) {
+    let puzzle_path = config().puzzle_path(day);
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read {puzzle_path:?}, run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let blocks = code_blocks(&puzzle);
+    let answers = expected_answers(&puzzle);
+
+    match &pick {
+        Some(pick) => write_examples(day, &blocks, pick),
+        None => print_blocks(&blocks),
+    }
+
+    for (part, answer) in (1..=2).zip(&answers) {
+        if let Some(answer) = answer {
+            println!("Expected answer of part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}");
+        }
+    }
+
+    if write_tests {
+        write_expected_answers(day, &answers);
+    } else if pick.is_none() && answers.iter().any(Option::is_some) {
+        println!("---");
+        println!(
+            "🎄 Type `cargo examples {day} --pick 1` to write examples, add `--tests` to write the expected answers into the tests."
+        );
+    }
+}
+
+fn print_blocks(blocks: &[CodeBlock]) {
+    if blocks.is_empty() {
+        println!("No code blocks found.");
+        return;
+    }
+
+    for block in blocks {
+        let line_count = block.content.lines().count();
+        println!(
+            "{ANSI_BOLD}[{}]{ANSI_RESET} {ANSI_ITALIC}part {}, {line_count} line(s){ANSI_RESET}",
+            block.index, block.part
+        );
+        for line in block.content.lines().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if line_count > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+}
+
+fn write_examples(day: Day, blocks: &[CodeBlock], pick: &[usize]) {
+    for (i, index) in pick.iter().enumerate() {
+        let Some(block) = blocks.iter().find(|b| b.index == *index) else {
+            eprintln!(
+                "There is no code block {index}, expecting one of 1 to {}.",
+                blocks.len()
+            );
+            process::exit(1);
+        };
+
+        let path = if i == 0 {
+            config().example_path(day)
+        } else {
+            config().data_file(&config().paths.examples, &format!("{day}-{index}.txt"))
+        };
+
+        write_file(&path, &block.content);
+        println!("Wrote code block {index} to {path:?}");
+    }
+}
+
+fn write_expected_answers(day: Day, answers: &[Option<String>; 2]) {
+    let module_path = config().bin_path(day);
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        eprintln!("Could not read {module_path:?}, run `cargo scaffold {day}` first.");
+        process::exit(1);
+    };
+
+    for (part, answer) in (1..=2).zip(answers) {
+        let Some(answer) = answer else { continue };
+
+        // NOTE: the module template returns `Option<u64>`, other answers have to be written by hand.
+        if answer.parse::<i128>().is_err() {
+            println!("Skipping non-numeric answer of part {part}.");
+            continue;
+        }
+
+        match fill_expected_answer(&module, part, answer) {
+            Some(filled) => {
+                module = filled;
+                println!("Wrote expected answer of part {part} into its test.");
+            }
+            None => println!("Test of part {part} was already filled in, skipping."),
+        }
+    }
+
+    write_file(&module_path, &module);
+}
+
+fn write_file(path: &Path, contents: &str) {
+    if let Err(e) = fs::write(path, contents) {
+        eprintln!("Failed to write {path:?}: {e}");
+        process::exit(1);
+    }
+}
@@ -1,17 +1,30 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{Day, aoc_cli};
+use crate::template::{
+    Day,
+    aoc_client::client,
+    config,
+    markdown::{page, render, terminal_width},
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+/// Renders the downloaded puzzle description of a day.
+/// The description is only fetched if it was not downloaded yet, or if `refresh` is set.
+pub fn handle(day: Day, refresh: bool) {
+    let puzzle_path = config().puzzle_path(day);
+
+    if refresh || !puzzle_path.exists() {
+        if let Err(e) = client().download(day) {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        }
+        println!();
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    match fs::read_to_string(&puzzle_path) {
+        Ok(puzzle) => page(&render(&puzzle, terminal_width())),
+        Err(e) => {
+            eprintln!("failed to read {puzzle_path:?}: {e}");
+            process::exit(1);
+        }
+    }
 }
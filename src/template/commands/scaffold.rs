@@ -1,79 +1,352 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, OpenOptions},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    Day, config, event_days,
+    examples::{code_blocks, expected_answers, fill_expected_answer, find_test, puzzle_title},
+};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Templates that ship with the crate, used if the templates directory does not provide them.
+const BUILTIN_TEMPLATES: [(&str, &str); 5] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "lines",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/lines.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+    (
+        "phases",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/phases.txt"
+        )),
+    ),
+];
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+const TEST_TEMPLATE: &str = "    #[test]
+    fn test_part_%PART%() {
+        let result = part_%PART%(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
     }
-    file.truncate(true).write(true).open(path)
+";
+
+fn is_empty(path: &Path) -> bool {
+    fs::read_to_string(path).map_or(true, |s| s.trim().is_empty())
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+fn write_file(path: &Path, contents: &str) {
+    if let Err(e) = fs::write(path, contents) {
+        eprintln!("Failed to write {path:?}: {e}");
+        process::exit(1);
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffolds the module and data files of a day without touching anything that has content.
+/// With `part` 2, an existing module is updated for the second part instead.
+pub fn handle(day: Day, overwrite: bool, part: u8, template: &str) {
+    let days = event_days();
+    if !days.contains(day) {
+        eprintln!("Day {day} is not part of the event, expecting one of {days}.");
+        process::exit(1);
+    }
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    let module_path = config().bin_path(day);
+    let puzzle = fs::read_to_string(config().puzzle_path(day)).ok();
+
+    if part == 1 {
+        create_module(day, &module_path, overwrite, template, puzzle.as_deref());
+        create_data_file("input", &config().input_path(day));
+        create_data_file("example", &config().example_path(day));
+    } else if !module_path.exists() {
+        eprintln!("Module file {module_path:?} does not exist, run `cargo scaffold {day}` first.");
+        process::exit(1);
+    }
+
+    let mut module = fs::read_to_string(&module_path).unwrap_or_default();
+
+    for test_part in 1..=part {
+        if let Some(updated) = add_missing_test(&module, test_part) {
+            module = updated;
+            println!("Added test of part {test_part} to {module_path:?}");
         }
+    }
+
+    match puzzle {
+        Some(puzzle) => module = apply_puzzle(day, part, &puzzle, module),
+        None => println!(
+            "No puzzle description found, run `cargo download {day}` to fill in examples and answers."
+        ),
+    }
+
+    write_file(&module_path, &module);
+
+    println!("---");
+    println!("🎄 Type `cargo solve {day}` to run your solution.");
+}
+
+/// Reads a template from the templates directory, falling back to the built-in templates.
+fn load_template(name: &str) -> Option<String> {
+    fs::read_to_string(config().paths.templates.join(format!("{name}.txt")))
+        .ok()
+        .or_else(|| {
+            BUILTIN_TEMPLATES
+                .iter()
+                .find(|(builtin, _)| *builtin == name)
+                .map(|(_, template)| (*template).to_string())
+        })
+}
+
+/// Names of all templates, built-in and from the templates directory.
+fn template_names() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(&config().paths.templates) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "txt").then(|| path.file_stem()?.to_str().map(String::from))?
+        }));
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Substitutes the placeholders of a module template.
+/// Without a configured year, lines that need it (like the puzzle URL) are left out.
+fn render_template(template: &str, day: Day, year: Option<u16>, title: Option<&str>) -> String {
+    let template = match year {
+        Some(year) => template.replace("%YEAR%", &year.to_string()),
+        None => template
+            .split_inclusive('\n')
+            .filter(|line| !line.contains("%YEAR%"))
+            .collect(),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace(
+            "%TITLE%",
+            &title.map_or_else(|| format!("Day {}", day.into_inner()), String::from),
+        )
+}
+
+fn create_module(day: Day, path: &Path, overwrite: bool, template: &str, puzzle: Option<&str>) {
+    if path.exists() && !overwrite {
+        println!("Keeping existing module file {path:?}");
+        return;
+    }
+
+    let Some(contents) = load_template(template) else {
+        eprintln!(
+            "Unknown template \"{template}\", expecting one of: {}.",
+            template_names().join(", ")
+        );
+        process::exit(1);
+    };
+
+    let title = puzzle.and_then(puzzle_title);
+    write_file(
+        path,
+        &render_template(&contents, day, config().year, title.as_deref()),
+    );
+    println!("Created module file {path:?} from template \"{template}\"");
+}
+
+fn create_data_file(name: &str, path: &Path) {
+    if !is_empty(path) {
+        println!("Keeping existing {name} file {path:?}");
+        return;
+    }
+
+    match OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+    {
+        Ok(_) => println!("Created empty {name} file {path:?}"),
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to create {name} file: {e}");
             process::exit(1);
         }
     }
+}
+
+/// Fills empty example files and placeholder assertions from the puzzle description.
+fn apply_puzzle(day: Day, part: u8, puzzle: &str, mut module: String) -> String {
+    let blocks = code_blocks(puzzle);
+    let answers = expected_answers(puzzle);
+
+    let example_path = config().example_path(day);
+    if let Some(block) = blocks.first()
+        && is_empty(&example_path)
+    {
+        write_file(&example_path, &block.content);
+        println!("Wrote first code block to {example_path:?}");
+    }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    // part two often comes with its own example, which the part two test should read.
+    if part == 2
+        && let Some(block) = blocks.iter().find(|b| b.part == 2)
+    {
+        let path = config().data_file(&config().paths.examples, &format!("{day}-2.txt"));
+        if is_empty(&path) {
+            write_file(&path, &block.content);
+            println!("Wrote first code block of part two to {path:?}");
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        if let Some(updated) = use_part_example(&module) {
+            module = updated;
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    for (answer_part, answer) in (1..=part).zip(answers) {
+        let Some(answer) = answer.filter(|a| a.parse::<i128>().is_ok()) else {
+            continue;
+        };
+        if let Some(updated) = fill_expected_answer(&module, answer_part, &answer) {
+            module = updated;
+            println!("Inserted expected answer {answer} into the test of part {answer_part}");
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    module
+}
+
+/// Appends the test of a part to the `tests` module, if it is missing.
+fn add_missing_test(module: &str, part: u8) -> Option<String> {
+    if find_test(module, part).is_some() {
+        return None;
+    }
+
+    let test = TEST_TEMPLATE.replace("%PART%", if part == 1 { "one" } else { "two" });
+    let mut updated = module.trim_end().to_string();
+
+    if updated.contains("mod tests {") && updated.ends_with('}') {
+        updated.pop();
+        updated = format!("{}\n\n{test}}}\n", updated.trim_end());
+    } else {
+        updated =
+            format!("{updated}\n\n#[cfg(test)]\nmod tests {{\n    use super::*;\n\n{test}}}\n");
+    }
+
+    Some(updated)
+}
+
+/// Points the part two test at the part two example `NN-2.txt`, if it still reads the default one.
+fn use_part_example(module: &str) -> Option<String> {
+    const DEFAULT: &str = "read_file(\"examples\", DAY)";
+
+    let test = find_test(module, 2)?;
+    let offset = test.start + module[test].find(DEFAULT)?;
+
+    let mut updated = module.to_string();
+    updated.replace_range(
+        offset..offset + DEFAULT.len(),
+        "read_file_part(\"examples\", DAY, 2)",
+    );
+    Some(updated)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BUILTIN_TEMPLATES, add_missing_test, render_template, use_part_example};
+    use crate::day;
+
+    const MODULE_TEMPLATE: &str = BUILTIN_TEMPLATES[0].1;
+
+    #[test]
+    fn renders_placeholders() {
+        let module = render_template(
+            MODULE_TEMPLATE,
+            day!(5),
+            Some(2025),
+            Some("Day 5: Cafeteria"),
+        );
+        assert!(
+            module
+                .starts_with("//! # Day 5: Cafeteria\n//! <https://adventofcode.com/2025/day/5>\n")
+        );
+        assert!(module.contains("advent_of_code::solution!(5);"));
+
+        let module = render_template("%DAY% %TITLE%", day!(5), None, None);
+        assert_eq!(module, "05 Day 5");
+
+        let module = render_template(MODULE_TEMPLATE, day!(5), None, None);
+        assert!(module.starts_with("//! # Day 5\n\nadvent_of_code::solution!(5);"));
+        assert!(!module.contains("adventofcode.com"));
+    }
+
+    #[test]
+    fn keeps_existing_tests() {
+        assert_eq!(add_missing_test(MODULE_TEMPLATE, 1), None);
+        assert_eq!(add_missing_test(MODULE_TEMPLATE, 2), None);
+    }
+
+    #[test]
+    fn adds_missing_tests() {
+        let module = "pub fn part_one(input: &str) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part_one(\"\"), None);
+    }
+}
+";
+        let updated = add_missing_test(module, 2).unwrap();
+        assert!(updated.contains("    }\n\n    #[test]\n    fn test_part_two() {"));
+        assert!(updated.ends_with("    }\n}\n"));
+        assert_eq!(add_missing_test(&updated, 2), None);
+    }
+
+    #[test]
+    fn adds_missing_tests_module() {
+        let updated = add_missing_test("pub fn part_one() {}\n", 1).unwrap();
+        assert!(updated.contains("#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]"));
+    }
+
+    #[test]
+    fn points_part_two_at_its_example() {
+        let updated = use_part_example(MODULE_TEMPLATE).unwrap();
+        assert!(
+            updated.contains("part_one(&advent_of_code::template::read_file(\"examples\", DAY))")
+        );
+        assert!(updated.contains("read_file_part(\"examples\", DAY, 2)"));
+        assert_eq!(use_part_example(&updated), None);
+    }
 }
@@ -0,0 +1,94 @@
+use std::process;
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, all_days, answers::AnswerBook, run_multi::child_commands,
+};
+
+/// Runs every day that has accepted answers and fails if any of them changed.
+pub fn handle(is_release: bool) {
+    let mut verified = 0;
+    let mut regressions: Vec<(Day, u8)> = vec![];
+
+    for day in all_days() {
+        let book = match AnswerBook::read_with_puzzle(day) {
+            Ok(book) => book,
+            Err(e) => {
+                eprintln!("failed to read answer book of day {day}: {e}");
+                process::exit(1);
+            }
+        };
+
+        let parts: Vec<u8> = [1, 2]
+            .into_iter()
+            .filter(|part| book.accepted(*part).is_some())
+            .collect();
+
+        if parts.is_empty() {
+            continue;
+        }
+
+        if verified + regressions.len() > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let output = child_commands::run_solution(day, false, is_release).unwrap_or_default();
+
+        for part in parts {
+            if is_verified(&output, part) {
+                verified += 1;
+            } else {
+                regressions.push((day, part));
+            }
+        }
+    }
+
+    println!();
+
+    if regressions.is_empty() {
+        println!("{ANSI_BOLD}Verified {verified} accepted answer(s).{ANSI_RESET}");
+        return;
+    }
+
+    eprintln!(
+        "{ANSI_BOLD}{} of {} accepted answer(s) regressed:{ANSI_RESET}",
+        regressions.len(),
+        verified + regressions.len()
+    );
+    for (day, part) in regressions {
+        eprintln!("  Day {day}, part {part}");
+    }
+    process::exit(1);
+}
+
+/// Checks whether the output of a solution marks a part as matching its accepted answer.
+fn is_verified(output: &[String], part: u8) -> bool {
+    let prefix = format!("Part {part}:");
+    output
+        .iter()
+        .any(|line| line.starts_with(&prefix) && line.trim_end().ends_with(" ✓"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_verified;
+
+    #[test]
+    fn detects_verified_parts() {
+        let output = [
+            "Part 1: 42 (1.2ms) ✓".to_string(),
+            "Part 2: 41 (3.4ms) ✗ (accepted answer: 42)".to_string(),
+        ];
+        assert!(is_verified(&output, 1));
+        assert!(!is_verified(&output, 2));
+    }
+
+    #[test]
+    fn detects_missing_parts() {
+        let output = ["Part 1: ✖ ✗ (accepted answer: 42)             ".to_string()];
+        assert!(!is_verified(&output, 1));
+        assert!(!is_verified(&output, 2));
+    }
+}
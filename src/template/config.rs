@@ -0,0 +1,583 @@
+/// Typed loader for the project configuration in `aoc.toml`.
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use toml::{Table, Value};
+
+use crate::template::{Day, EventDays};
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Project configuration. Every value has a default, so `aoc.toml` and each of its tables are optional.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    /// The event year. Overridden by the `AOC_YEAR` environment variable.
+    pub year: Option<u16>,
+    /// The days of the event. Overridden by the `AOC_DAYS` environment variable.
+    pub days: Option<EventDays>,
+    pub paths: Paths,
+    pub readme: Readme,
+    pub benchmark: Benchmark,
+    pub timeouts: Timeouts,
+    pub session: Session,
+    pub client: Client,
+}
+
+/// Locations of data files and solution modules, relative to the project root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paths {
+    pub data: PathBuf,
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub answers: String,
+    pub timings: PathBuf,
+    pub bin: PathBuf,
+    /// Directory of the module templates used by `cargo scaffold`.
+    pub templates: PathBuf,
+}
+
+/// Location of the README and the markers that enclose generated content.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Readme {
+    pub path: PathBuf,
+    pub benchmarks_marker: String,
+}
+
+/// Bounds for the number of iterations when benching a solution with `--time`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Benchmark {
+    pub min_iterations: u128,
+    pub max_iterations: u128,
+    /// Iterations are chosen so that a bench takes roughly this long.
+    pub target: Duration,
+}
+
+/// Limits for requests and external processes. [`None`] waits indefinitely.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timeouts {
+    pub aoc: Option<Duration>,
+    pub solution: Option<Duration>,
+}
+
+/// Where the adventofcode.com session token is read from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Session {
+    pub env: String,
+    pub file: Option<PathBuf>,
+}
+
+/// How the template talks to adventofcode.com.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Client {
+    pub backend: Backend,
+    pub base_url: String,
+    /// Minimum time between two requests to adventofcode.com.
+    pub request_interval: Duration,
+    /// How the maintainers of adventofcode.com can reach you, sent in the User-Agent.
+    pub contact: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in HTTP client.
+    Native,
+    /// Shells out to the `aoc` binary of aoc-cli.
+    AocCli,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            data: "data".into(),
+            inputs: "inputs".into(),
+            examples: "examples".into(),
+            puzzles: "puzzles".into(),
+            answers: "answers".into(),
+            timings: "data/timings.json".into(),
+            bin: "src/bin".into(),
+            templates: "src/templates".into(),
+        }
+    }
+}
+
+impl Default for Readme {
+    fn default() -> Self {
+        Self {
+            path: "README.md".into(),
+            benchmarks_marker: "<!--- benchmarking table --->".into(),
+        }
+    }
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self {
+            min_iterations: 10,
+            max_iterations: 10000,
+            target: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            aoc: Some(Duration::from_secs(60)),
+            solution: None,
+        }
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            env: "ADVENT_OF_CODE_SESSION".into(),
+            file: Some(expand_home("~/.adventofcode.session")),
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self {
+            backend: Backend::Native,
+            base_url: "https://adventofcode.com".into(),
+            request_interval: Duration::from_secs(2),
+            contact: None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IO(io::Error),
+    Parser(String),
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        ConfigError::IO(e)
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::IO(e) => write!(f, "could not read {CONFIG_FILE_PATH}: {e}"),
+            ConfigError::Parser(e) => write!(f, "invalid configuration: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the project configuration, loading it on first access.
+///
+/// Exits the process if `aoc.toml` exists but cannot be parsed.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(|| match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    })
+}
+
+impl Config {
+    /// Reads `aoc.toml` (or the file in `AOC_CONFIG`) and applies environment overrides.
+    /// Returns the default configuration if the file does not exist.
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = env::var("AOC_CONFIG").unwrap_or_else(|_| CONFIG_FILE_PATH.into());
+
+        let mut config = match fs::read_to_string(&path) {
+            Ok(s) => Self::try_from(s.as_str())?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Ok(year) = env::var("AOC_YEAR") {
+            config.year = Some(parse_year(&year)?);
+        }
+
+        if let Ok(days) = env::var("AOC_DAYS") {
+            config.days = Some(parse_days(&days)?);
+        }
+
+        Ok(config)
+    }
+
+    /// Returns the days of the configured event.
+    pub fn event_days(&self) -> EventDays {
+        self.days
+            .or_else(|| self.year.map(EventDays::for_year))
+            .unwrap_or_default()
+    }
+
+    /// The configured directory for one of the data folder names `inputs`, `examples`,
+    /// `puzzles` and `answers`. Any other name is used as is.
+    pub fn folder<'a>(&'a self, name: &'a str) -> &'a str {
+        match name {
+            "inputs" => &self.paths.inputs,
+            "examples" => &self.paths.examples,
+            "puzzles" => &self.paths.puzzles,
+            "answers" => &self.paths.answers,
+            _ => name,
+        }
+    }
+
+    /// Path of a data file for a day, e.g. `data/inputs/01.txt`.
+    pub fn data_file(&self, folder: &str, file_name: &str) -> PathBuf {
+        self.paths.data.join(folder).join(file_name)
+    }
+
+    pub fn input_path(&self, day: Day) -> PathBuf {
+        self.data_file(&self.paths.inputs, &format!("{day}.txt"))
+    }
+
+    pub fn example_path(&self, day: Day) -> PathBuf {
+        self.data_file(&self.paths.examples, &format!("{day}.txt"))
+    }
+
+    pub fn puzzle_path(&self, day: Day) -> PathBuf {
+        self.data_file(&self.paths.puzzles, &format!("{day}.md"))
+    }
+
+    pub fn answers_path(&self, day: Day) -> PathBuf {
+        self.data_file(&self.paths.answers, &format!("{day}.json"))
+    }
+
+    pub fn bin_path(&self, day: Day) -> PathBuf {
+        self.paths.bin.join(format!("{day}.rs"))
+    }
+
+    /// Returns the session token from the configured environment variable, or from the session file.
+    pub fn session_token(&self) -> Option<String> {
+        if let Ok(token) = env::var(&self.session.env) {
+            return Some(token.trim().to_string());
+        }
+
+        let file = self.session.file.as_ref()?;
+        fs::read_to_string(file)
+            .ok()
+            .map(|token| token.trim().to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_year(s: &str) -> Result<u16, ConfigError> {
+    s.trim()
+        .parse()
+        .map_err(|_| ConfigError::Parser(format!("expected `year` to be a number, got `{s}`.")))
+}
+
+fn parse_days(s: &str) -> Result<EventDays, ConfigError> {
+    s.parse()
+        .map_err(|e| ConfigError::Parser(format!("invalid `days` value `{s}`: {e}.")))
+}
+
+/// Typed accessors for a TOML table, producing errors that name the offending key.
+struct Section<'a> {
+    name: &'a str,
+    table: Option<&'a Table>,
+}
+
+impl<'a> Section<'a> {
+    fn new(name: &'a str, table: &'a Table) -> Result<Self, ConfigError> {
+        match table.get(name) {
+            None => Ok(Self { name, table: None }),
+            Some(Value::Table(table)) => Ok(Self {
+                name,
+                table: Some(table),
+            }),
+            Some(_) => Err(ConfigError::Parser(format!(
+                "expected `{name}` to be a table."
+            ))),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&'a Value> {
+        self.table?.get(key)
+    }
+
+    fn error(&self, key: &str, expected: &str) -> ConfigError {
+        ConfigError::Parser(format!("expected `{}.{key}` to be {expected}.", self.name))
+    }
+
+    fn string(&self, key: &str) -> Result<Option<String>, ConfigError> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(self.error(key, "a string")),
+        }
+    }
+
+    fn integer(&self, key: &str) -> Result<Option<u64>, ConfigError> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Integer(i)) if *i >= 0 => Ok(Some(*i as u64)),
+            Some(_) => Err(self.error(key, "a non-negative integer")),
+        }
+    }
+
+    /// A duration in seconds, where `0` disables the limit.
+    fn timeout(&self, key: &str) -> Result<Option<Option<Duration>>, ConfigError> {
+        Ok(self
+            .integer(key)?
+            .map(|secs| (secs > 0).then(|| Duration::from_secs(secs))))
+    }
+}
+
+impl TryFrom<&str> for Config {
+    type Error = ConfigError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let table: Table = value
+            .parse()
+            .map_err(|e: toml::de::Error| ConfigError::Parser(e.message().to_string()))?;
+
+        let year =
+            match table.get("year") {
+                None => None,
+                Some(Value::Integer(year)) => Some(u16::try_from(*year).map_err(|_| {
+                    ConfigError::Parser("expected `year` to be a valid year.".into())
+                })?),
+                Some(_) => {
+                    return Err(ConfigError::Parser(
+                        "expected `year` to be an integer.".into(),
+                    ));
+                }
+            };
+
+        let days = match table.get("days") {
+            None => None,
+            Some(Value::String(days)) => Some(parse_days(days)?),
+            Some(Value::Integer(days)) => Some(parse_days(&days.to_string())?),
+            Some(_) => {
+                return Err(ConfigError::Parser(
+                    "expected `days` to be the last day or a range like \"1-12\".".into(),
+                ));
+            }
+        };
+
+        let mut config = Config {
+            year,
+            days,
+            ..Config::default()
+        };
+
+        let paths = Section::new("paths", &table)?;
+        if let Some(data) = paths.string("data")? {
+            config.paths.data = data.into();
+        }
+        if let Some(inputs) = paths.string("inputs")? {
+            config.paths.inputs = inputs;
+        }
+        if let Some(examples) = paths.string("examples")? {
+            config.paths.examples = examples;
+        }
+        if let Some(puzzles) = paths.string("puzzles")? {
+            config.paths.puzzles = puzzles;
+        }
+        if let Some(answers) = paths.string("answers")? {
+            config.paths.answers = answers;
+        }
+        if let Some(timings) = paths.string("timings")? {
+            config.paths.timings = timings.into();
+        }
+        if let Some(bin) = paths.string("bin")? {
+            config.paths.bin = bin.into();
+        }
+        if let Some(templates) = paths.string("templates")? {
+            config.paths.templates = templates.into();
+        }
+
+        let readme = Section::new("readme", &table)?;
+        if let Some(path) = readme.string("path")? {
+            config.readme.path = path.into();
+        }
+        if let Some(marker) = readme.string("benchmarks_marker")? {
+            config.readme.benchmarks_marker = marker;
+        }
+
+        let benchmark = Section::new("benchmark", &table)?;
+        if let Some(min) = benchmark.integer("min_iterations")? {
+            config.benchmark.min_iterations = u128::from(min.max(1));
+        }
+        if let Some(max) = benchmark.integer("max_iterations")? {
+            config.benchmark.max_iterations = u128::from(max);
+        }
+        if let Some(millis) = benchmark.integer("target_millis")? {
+            config.benchmark.target = Duration::from_millis(millis);
+        }
+        if config.benchmark.min_iterations > config.benchmark.max_iterations {
+            return Err(ConfigError::Parser(
+                "expected `benchmark.min_iterations` to not exceed `benchmark.max_iterations`."
+                    .into(),
+            ));
+        }
+
+        let timeouts = Section::new("timeouts", &table)?;
+        if let Some(aoc) = timeouts.timeout("aoc_secs")? {
+            config.timeouts.aoc = aoc;
+        }
+        if let Some(solution) = timeouts.timeout("solution_secs")? {
+            config.timeouts.solution = solution;
+        }
+
+        let session = Section::new("session", &table)?;
+        if let Some(env) = session.string("env")? {
+            config.session.env = env;
+        }
+        if let Some(file) = session.string("file")? {
+            config.session.file = Some(expand_home(&file));
+        }
+
+        let client = Section::new("client", &table)?;
+        if let Some(backend) = client.string("backend")? {
+            config.client.backend = match backend.as_str() {
+                "native" => Backend::Native,
+                "aoc-cli" => Backend::AocCli,
+                _ => return Err(client.error("backend", "either \"native\" or \"aoc-cli\"")),
+            };
+        }
+        if let Some(base_url) = client.string("base_url")? {
+            config.client.base_url = base_url;
+        }
+        if let Some(secs) = client.integer("request_interval_secs")? {
+            config.client.request_interval = Duration::from_secs(secs);
+        }
+        if let Some(contact) = client.string("contact")? {
+            config.client.contact = Some(contact);
+        }
+
+        Ok(config)
+    }
+}
+
+/// Expands a leading `~` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Backend, Config};
+    use crate::day;
+
+    #[test]
+    fn handles_empty_config() {
+        let config = Config::try_from("").unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn handles_full_config() {
+        let config = Config::try_from(
+            r#"
+            year = 2024
+            days = "1-20"
+
+            [paths]
+            data = "puzzle_data"
+            inputs = "in"
+            bin = "solutions"
+            templates = "solutions/templates"
+
+            [readme]
+            path = "docs/README.md"
+            benchmarks_marker = "<!-- bench -->"
+
+            [benchmark]
+            min_iterations = 5
+            max_iterations = 50
+            target_millis = 200
+
+            [timeouts]
+            aoc_secs = 0
+            solution_secs = 30
+
+            [session]
+            env = "AOC_TOKEN"
+            file = "/tmp/session"
+
+            [client]
+            backend = "aoc-cli"
+            base_url = "http://localhost:8080"
+            request_interval_secs = 0
+            contact = "me@example.com"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2024));
+        assert_eq!(config.event_days().last(), day!(20));
+        assert_eq!(
+            config.input_path(day!(3)).to_str(),
+            Some("puzzle_data/in/03.txt")
+        );
+        assert_eq!(
+            config.example_path(day!(3)).to_str(),
+            Some("puzzle_data/examples/03.txt")
+        );
+        assert_eq!(config.folder("inputs"), "in");
+        assert_eq!(config.folder("examples"), "examples");
+        assert_eq!(config.folder("custom"), "custom");
+        assert_eq!(config.bin_path(day!(3)).to_str(), Some("solutions/03.rs"));
+        assert_eq!(config.paths.templates.to_str(), Some("solutions/templates"));
+        assert_eq!(config.readme.benchmarks_marker, "<!-- bench -->");
+        assert_eq!(config.benchmark.min_iterations, 5);
+        assert_eq!(config.benchmark.max_iterations, 50);
+        assert_eq!(config.benchmark.target, Duration::from_millis(200));
+        assert_eq!(config.timeouts.aoc, None);
+        assert_eq!(config.timeouts.solution, Some(Duration::from_secs(30)));
+        assert_eq!(config.session.env, "AOC_TOKEN");
+        assert_eq!(config.client.backend, Backend::AocCli);
+        assert_eq!(config.client.base_url, "http://localhost:8080");
+        assert_eq!(config.client.request_interval, Duration::ZERO);
+        assert_eq!(config.client.contact.as_deref(), Some("me@example.com"));
+    }
+
+    #[test]
+    fn derives_event_days_from_year() {
+        let config = Config::try_from("year = 2023").unwrap();
+        assert_eq!(config.event_days().last(), day!(25));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_types() {
+        Config::try_from("[paths]\ndata = 1").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_backend() {
+        Config::try_from("[client]\nbackend = \"curl\"").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_inverted_iteration_bounds() {
+        Config::try_from("[benchmark]\nmin_iterations = 10\nmax_iterations = 5").unwrap();
+    }
+}
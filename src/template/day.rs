@@ -2,6 +2,8 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::config;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
@@ -14,7 +16,7 @@ const SERVER_UTC_OFFSET: i32 = -5;
 /// This value displays as a two digit number.
 ///
 /// ```
-/// # use advent_of_code::Day;
+/// # use advent_of_code::template::Day;
 /// let day = Day::new(8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
@@ -39,15 +41,14 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a december day that is part of the configured event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
+        if today.month() != 12 {
+            return None;
         }
+        Self::new(u8::try_from(today.day()).ok()?).filter(|day| event_days().contains(*day))
     }
 }
 
@@ -94,20 +95,125 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// The first year in which the event was shortened to 12 days.
+const SHORT_EVENT_YEAR: u16 = 2025;
+
+/// An inclusive range of days that make up an event.
+///
+/// Events up to 2024 ran from the 1st to the 25th, later events only have 12 puzzles.
+/// The range can be overridden with `days` in `aoc.toml` or the `AOC_DAYS` environment variable,
+/// either as the number of the last day (e.g. `12`) or as a range (e.g. `3-12`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventDays {
+    first: Day,
+    last: Day,
+}
+
+impl EventDays {
+    /// Creates an [`EventDays`] range if `first` is not after `last`, returns [`None`] otherwise.
+    pub const fn new(first: Day, last: Day) -> Option<Self> {
+        if first.0 > last.0 {
+            return None;
+        }
+        Some(Self { first, last })
+    }
+
+    /// Returns the days of the event held in `year`.
+    pub const fn for_year(year: u16) -> Self {
+        let last = if year >= SHORT_EVENT_YEAR { 12 } else { 25 };
+        Self {
+            first: Day(1),
+            last: Day(last),
+        }
+    }
+
+    /// Returns the first day of the event.
+    pub fn first(&self) -> Day {
+        self.first
+    }
+
+    /// Returns the last day of the event.
+    pub fn last(&self) -> Day {
+        self.last
+    }
+
+    /// Returns `true` if `day` is part of the event.
+    pub fn contains(&self, day: Day) -> bool {
+        self.first <= day && day <= self.last
+    }
+
+    /// Returns an iterator over every day of the event.
+    pub fn iter(&self) -> AllDays {
+        AllDays::new(*self)
+    }
+}
+
+impl Default for EventDays {
+    fn default() -> Self {
+        Self::for_year(SHORT_EVENT_YEAR)
+    }
+}
+
+impl Display for EventDays {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "days {} to {}", self.first, self.last)
+    }
+}
+
+impl FromStr for EventDays {
+    type Err = EventDaysFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, last) = match s.split_once('-') {
+            Some((first, last)) => (first.trim().parse(), last.trim().parse()),
+            None => (Ok(Day(1)), s.trim().parse()),
+        };
+        let first = first.map_err(|_| EventDaysFromStrError)?;
+        let last = last.map_err(|_| EventDaysFromStrError)?;
+        Self::new(first, last).ok_or(EventDaysFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing an [`EventDays`] range.
+#[derive(Debug)]
+pub struct EventDaysFromStrError;
+
+impl Error for EventDaysFromStrError {}
+
+impl Display for EventDaysFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting the last day of the event (e.g. `12`) or a range of days (e.g. `1-12`)",
+        )
+    }
+}
+
+/// Returns the days of the configured event.
+///
+/// Uses the configured `days` if set, and falls back to the length of the event in the configured year otherwise.
+pub fn event_days() -> EventDays {
+    config().event_days()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields every day of the configured event.
 pub fn all_days() -> AllDays {
-    AllDays::new()
+    event_days().iter()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of an [`EventDays`] range.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(days: EventDays) -> Self {
+        Self {
+            current: days.first.0,
+            last: days.last.0,
+        }
     }
 }
 
@@ -115,10 +221,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at a valid day and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -143,11 +249,11 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{AllDays, Day, EventDays};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = EventDays::for_year(2024).iter();
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -176,6 +282,35 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn short_event_iterator() {
+        let days: Vec<Day> = EventDays::for_year(2025).iter().collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.first(), Some(&Day(1)));
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
+
+    #[test]
+    fn custom_range_iterator() {
+        let days: Vec<Day> = AllDays::new(EventDays::new(Day(3), Day(5)).unwrap()).collect();
+        assert_eq!(days, vec![Day(3), Day(4), Day(5)]);
+    }
+
+    #[test]
+    fn parses_event_days() {
+        let days: EventDays = "12".parse().unwrap();
+        assert_eq!(days, EventDays::for_year(2025));
+
+        let days: EventDays = "3-7".parse().unwrap();
+        assert_eq!(days.first(), Day(3));
+        assert_eq!(days.last(), Day(7));
+        assert!(days.contains(Day(5)));
+        assert!(!days.contains(Day(8)));
+
+        assert!("7-3".parse::<EventDays>().is_err());
+        assert!("26".parse::<EventDays>().is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,214 @@
+/// Module that extracts the title, examples and expected answers from puzzle descriptions.
+/// Works on the Markdown written by `cargo download`, both by the native client and by aoc-cli.
+use std::{ops::Range, sync::LazyLock};
+
+use regex::Regex;
+
+/// Emphasised inline code, e.g. `*`143`*` (native client) or `` `*143*` `` (aoc-cli).
+static EMPHASISED_CODE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*`([^`\n]+)`\*|`\*([^*`\n]+)\*`").unwrap());
+
+/// e.g. `## --- Day 5: Cafeteria ---`.
+static TITLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"--- (Day \d+: .+?) ---").unwrap());
+
+/// Returns the title of a puzzle description, e.g. `Day 5: Cafeteria`.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    TITLE
+        .captures(markdown)
+        .map(|captures| captures[1].trim().to_string())
+}
+
+/// A code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// 1-based position of the block in the description.
+    pub index: usize,
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub content: String,
+}
+
+fn is_part_two_heading(line: &str) -> bool {
+    line.contains("--- Part Two ---")
+}
+
+/// Returns all fenced and indented code blocks of a puzzle description.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut part = 1;
+    let mut lines = markdown.lines().peekable();
+
+    let mut push = |part: u8, content: Vec<&str>| {
+        blocks.push(CodeBlock {
+            index: blocks.len() + 1,
+            part,
+            content: content.join("\n") + "\n",
+        });
+    };
+
+    while let Some(line) = lines.next() {
+        if is_part_two_heading(line) {
+            part = 2;
+        }
+
+        let trimmed = line.trim_start();
+        if let Some(fence) = ["```", "~~~"].into_iter().find(|f| trimmed.starts_with(f)) {
+            let mut content = vec![];
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with(fence) {
+                    break;
+                }
+                content.push(line);
+            }
+            push(part, content);
+        } else if let Some(first) = line.strip_prefix("    ") {
+            let mut content = vec![first];
+            while let Some(line) = lines.next_if(|l| l.starts_with("    ") || l.is_empty()) {
+                content.push(line.get(4..).unwrap_or_default());
+            }
+            while content.last().is_some_and(|l| l.is_empty()) {
+                content.pop();
+            }
+            push(part, content);
+        }
+    }
+
+    blocks
+}
+
+/// Returns the expected example answer of each part, if the description emphasises one.
+/// By convention, the answer is the last emphasised code of a part's description.
+pub fn expected_answers(markdown: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let mut part = 0;
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if is_part_two_heading(line) {
+            part = 1;
+        }
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_code_block = !in_code_block;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        // NOTE: the puzzle file also lists the accepted answers, these are never emphasised.
+        if let Some(captures) = EMPHASISED_CODE.captures_iter(line).last() {
+            let answer = captures.get(1).or_else(|| captures.get(2)).unwrap();
+            answers[part] = Some(answer.as_str().trim().to_string());
+        }
+    }
+
+    answers
+}
+
+/// Returns the byte range of the generated test of a part, from its signature to the next function.
+pub fn find_test(module: &str, part: u8) -> Option<Range<usize>> {
+    let name = if part == 1 { "one" } else { "two" };
+    let start = module.find(&format!("fn test_part_{name}()"))?;
+    let end = module[start + 1..]
+        .find("fn ")
+        .map_or(module.len(), |i| start + 1 + i);
+    Some(start..end)
+}
+
+/// Replaces the placeholder assertion of the part's test with the expected answer.
+/// Returns `None` if the test does not exist or was already filled in.
+pub fn fill_expected_answer(module: &str, part: u8, answer: &str) -> Option<String> {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    let test = find_test(module, part)?;
+    let offset = test.start + module[test].find(PLACEHOLDER)?;
+
+    let mut filled = module.to_string();
+    filled.replace_range(
+        offset..offset + PLACEHOLDER.len(),
+        &format!("assert_eq!(result, Some({answer}));"),
+    );
+    Some(filled)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CodeBlock, code_blocks, expected_answers, fill_expected_answer, puzzle_title};
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+For example:
+
+```
+3   4
+4   3
+```
+
+After *`2`* steps, the total is *`11`*.
+
+## --- Part Two ---
+
+Consider the same example with `x` and:
+
+    1 2
+
+    3 4
+
+What is the score? In the example, it is `*31*`.
+
+Your puzzle answer was `1234`.
+";
+
+    #[test]
+    fn finds_title() {
+        assert_eq!(puzzle_title(PUZZLE), Some("Day 1: Test".to_string()));
+        assert_eq!(puzzle_title("No title here."), None);
+    }
+
+    #[test]
+    fn finds_code_blocks() {
+        assert_eq!(
+            code_blocks(PUZZLE),
+            vec![
+                CodeBlock {
+                    index: 1,
+                    part: 1,
+                    content: "3   4\n4   3\n".into()
+                },
+                CodeBlock {
+                    index: 2,
+                    part: 2,
+                    content: "1 2\n\n3 4\n".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_expected_answers() {
+        assert_eq!(
+            expected_answers(PUZZLE),
+            [Some("11".to_string()), Some("31".to_string())]
+        );
+        assert_eq!(expected_answers("No examples here."), [None, None]);
+    }
+
+    #[test]
+    fn fills_expected_answers() {
+        let module = "fn test_part_one() {
+    let result = part_one(\"\");
+    assert_eq!(result, None);
+}
+
+fn test_part_two() {
+    let result = part_two(\"\");
+    assert_eq!(result, None);
+}
+";
+        let filled = fill_expected_answer(module, 2, "31").unwrap();
+        assert!(filled.contains("assert_eq!(result, None);\n}\n\nfn test_part_two"));
+        assert!(filled.contains("assert_eq!(result, Some(31));"));
+        assert_eq!(fill_expected_answer(&filled, 2, "31"), None);
+    }
+}
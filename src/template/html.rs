@@ -0,0 +1,535 @@
+/// Module that converts the HTML served by adventofcode.com to Markdown.
+/// Only the small subset of HTML used in puzzle descriptions and submission responses is supported.
+use std::fmt::Write;
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Element {
+        tag: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+impl Node {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        match self {
+            Node::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+        }
+    }
+}
+
+/// An element whose closing tag has not been seen yet.
+struct OpenElement {
+    tag: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses an HTML document into a list of nodes.
+/// Unclosed elements are closed implicitly, stray closing tags are ignored.
+fn parse(html: &str) -> Vec<Node> {
+    // stack of open elements, the bottom entry collects the top-level nodes.
+    let mut stack = vec![OpenElement {
+        tag: String::new(),
+        attributes: vec![],
+        children: vec![],
+    }];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            if let Some(position) = stack.iter().rposition(|open| open.tag == name) {
+                // `position` is never 0, the root has an empty tag name.
+                while stack.len() > position {
+                    close_element(&mut stack);
+                }
+            }
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attributes) = parse_tag(tag);
+
+        // script and style contents are never rendered.
+        if name == "script" || name == "style" {
+            let closing = format!("</{name}");
+            rest = rest.find(&closing).map_or("", |end| &rest[end..]);
+            continue;
+        }
+
+        stack.push(OpenElement {
+            tag: name.clone(),
+            attributes,
+            children: vec![],
+        });
+
+        if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+            close_element(&mut stack);
+        }
+    }
+
+    while stack.len() > 1 {
+        close_element(&mut stack);
+    }
+
+    stack.pop().map(|root| root.children).unwrap_or_default()
+}
+
+fn push_text(stack: &mut [OpenElement], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(open) = stack.last_mut() {
+        open.children.push(Node::Text(decode_entities(text)));
+    }
+}
+
+fn close_element(stack: &mut Vec<OpenElement>) {
+    let Some(open) = stack.pop() else {
+        return;
+    };
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(Node::Element {
+            tag: open.tag,
+            attributes: open.attributes,
+            children: open.children,
+        });
+    }
+}
+
+fn parse_tag(tag: &str) -> (String, Vec<(String, String)>) {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let name = tag[..name_end].to_ascii_lowercase();
+
+    let mut attributes = vec![];
+    let mut rest = tag[name_end..].trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            let (value, remaining) = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value = &value[1..];
+                    let end = value.find(quote).unwrap_or(value.len());
+                    (&value[..end], value.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                    (&value[..end], &value[end..])
+                }
+            };
+            rest = remaining.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+
+        if !key.is_empty() {
+            attributes.push((key, value));
+        }
+    }
+
+    (name, attributes)
+}
+
+/// Decodes the named entities used on adventofcode.com and all numeric entities.
+pub fn decode_entities(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders nodes as Markdown. Relative links are resolved against `base_url`.
+struct Renderer<'a> {
+    base_url: &'a str,
+}
+
+impl Renderer<'_> {
+    fn blocks(&self, nodes: &[Node]) -> Vec<String> {
+        let mut blocks = vec![];
+        let mut inline = String::new();
+
+        for node in nodes {
+            match node {
+                Node::Element { tag, children, .. } if is_block(tag) => {
+                    flush_inline(&mut blocks, &mut inline);
+                    blocks.extend(self.block(node, tag, children));
+                }
+                _ => inline.push_str(&self.inline(node)),
+            }
+        }
+
+        flush_inline(&mut blocks, &mut inline);
+        blocks
+    }
+
+    fn block(&self, node: &Node, tag: &str, children: &[Node]) -> Vec<String> {
+        match tag {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = tag[1..].parse().unwrap_or(2);
+                vec![format!(
+                    "{} {}",
+                    "#".repeat(level),
+                    collapse_whitespace(&self.inlines(children))
+                )]
+            }
+            "pre" => {
+                let code = node.text();
+                vec![format!("```\n{}\n```", code.trim_end_matches('\n'))]
+            }
+            "ul" | "ol" => {
+                let items: Vec<String> = children
+                    .iter()
+                    .filter(|child| matches!(child, Node::Element { tag, .. } if tag == "li"))
+                    .enumerate()
+                    .map(|(index, item)| {
+                        let marker = if tag == "ol" {
+                            format!("{}. ", index + 1)
+                        } else {
+                            "- ".into()
+                        };
+                        let Node::Element { children, .. } = item else {
+                            unreachable!()
+                        };
+                        let content = self.blocks(children).join("\n\n");
+                        let indent = " ".repeat(marker.len());
+                        let content = content.replace('\n', &format!("\n{indent}"));
+                        format!("{marker}{content}")
+                    })
+                    .collect();
+                vec![items.join("\n")]
+            }
+            "hr" => vec!["---".into()],
+            "blockquote" => self
+                .blocks(children)
+                .into_iter()
+                .map(|block| format!("> {}", block.replace('\n', "\n> ")))
+                .collect(),
+            "p" => vec![collapse_whitespace(&self.inlines(children))],
+            _ => self.blocks(children),
+        }
+    }
+
+    fn inlines(&self, nodes: &[Node]) -> String {
+        nodes.iter().map(|node| self.inline(node)).collect()
+    }
+
+    fn inline(&self, node: &Node) -> String {
+        let Node::Element { tag, children, .. } = node else {
+            // newlines in the HTML source are plain whitespace, only `<br>` breaks a line.
+            return node.text().replace('\n', " ");
+        };
+
+        match tag.as_str() {
+            "em" | "i" | "b" | "strong" => {
+                let content = self.inlines(children);
+                if content.trim().is_empty() {
+                    content
+                } else {
+                    format!("*{content}*")
+                }
+            }
+            "code" => {
+                // emphasis inside inline code is moved outside, Markdown would render it literally.
+                if let [child @ Node::Element { tag, .. }] = children.as_slice()
+                    && tag == "em"
+                {
+                    format!("*`{}`*", child.text())
+                } else {
+                    format!("`{}`", node.text())
+                }
+            }
+            "a" => {
+                let text = self.inlines(children);
+                match node.attribute("href") {
+                    Some(href) => format!("[{text}]({})", self.resolve(href)),
+                    None => text,
+                }
+            }
+            "br" => "\n".into(),
+            _ => self.inlines(children),
+        }
+    }
+
+    fn resolve(&self, href: &str) -> String {
+        if href.contains("://") || href.starts_with('#') || href.starts_with("mailto:") {
+            href.into()
+        } else if href.starts_with('/') {
+            format!("{}{href}", self.base_url.trim_end_matches('/'))
+        } else {
+            format!("{}/{href}", self.base_url.trim_end_matches('/'))
+        }
+    }
+}
+
+fn is_block(tag: &str) -> bool {
+    matches!(
+        tag,
+        "h1" | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "p"
+            | "pre"
+            | "ul"
+            | "ol"
+            | "hr"
+            | "blockquote"
+            | "article"
+            | "div"
+            | "main"
+            | "section"
+    )
+}
+
+fn flush_inline(blocks: &mut Vec<String>, inline: &mut String) {
+    let text = collapse_whitespace(inline);
+    if !text.is_empty() {
+        blocks.push(text);
+    }
+    inline.clear();
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for (index, line) in s.split('\n').enumerate() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if index > 0 {
+            // a `<br>` becomes a hard line break.
+            result.push_str("  \n");
+        }
+        let _ = write!(result, "{}", words.join(" "));
+    }
+    result.trim().to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn find_all<'a>(nodes: &'a [Node], matches: &impl Fn(&Node) -> bool, found: &mut Vec<&'a Node>) {
+    for node in nodes {
+        if matches(node) {
+            found.push(node);
+        } else if let Node::Element { children, .. } = node {
+            find_all(children, matches, found);
+        }
+    }
+}
+
+fn has_class(node: &Node, class: &str) -> bool {
+    node.attribute("class")
+        .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+}
+
+fn is_tag(node: &Node, name: &str) -> bool {
+    matches!(node, Node::Element { tag, .. } if tag == name)
+}
+
+/// Converts an HTML fragment to Markdown.
+pub fn to_markdown(html: &str, base_url: &str) -> String {
+    let nodes = parse(html);
+    Renderer { base_url }.blocks(&nodes).join("\n\n")
+}
+
+/// Extracts the puzzle description from a puzzle page: every `article.day-desc`,
+/// followed by the answers that were already accepted (`Your puzzle answer was ...`).
+pub fn puzzle_to_markdown(html: &str, base_url: &str) -> String {
+    let nodes = parse(html);
+    let mut found = vec![];
+    find_all(
+        &nodes,
+        &|node| {
+            (is_tag(node, "article") && has_class(node, "day-desc"))
+                || (is_tag(node, "p") && node.text().starts_with("Your puzzle answer was"))
+        },
+        &mut found,
+    );
+
+    let renderer = Renderer { base_url };
+    let blocks: Vec<String> = found
+        .into_iter()
+        .flat_map(|node| match node {
+            Node::Element { tag, children, .. } => renderer.block(node, tag, children),
+            Node::Text(_) => vec![],
+        })
+        .collect();
+
+    blocks.join("\n\n") + "\n"
+}
+
+/// Extracts the message from a submission response page (the contents of `main > article`).
+pub fn response_to_markdown(html: &str, base_url: &str) -> String {
+    let nodes = parse(html);
+    let mut found = vec![];
+    find_all(&nodes, &|node| is_tag(node, "article"), &mut found);
+
+    let renderer = Renderer { base_url };
+    match found.first() {
+        Some(Node::Element { children, .. }) => renderer.blocks(children).join("\n\n"),
+        _ => renderer.blocks(&nodes).join("\n\n"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, puzzle_to_markdown, response_to_markdown, to_markdown};
+
+    const BASE_URL: &str = "https://adventofcode.com";
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c &gt; d"),
+            "a < b && c > d"
+        );
+        assert_eq!(decode_entities("&#39;&#x41;&quot;"), "'A\"");
+        assert_eq!(decode_entities("R&D; &unknown;"), "R&D; &unknown;");
+    }
+
+    #[test]
+    fn converts_inline_elements() {
+        let html =
+            r#"<p>The <em>answer</em> is <code>42</code>, see <a href="/2025/about">here</a>.</p>"#;
+        assert_eq!(
+            to_markdown(html, BASE_URL),
+            "The *answer* is `42`, see [here](https://adventofcode.com/2025/about)."
+        );
+    }
+
+    #[test]
+    fn moves_emphasis_out_of_code() {
+        let html = "<p>The total is <code><em>11</em></code>.</p>";
+        assert_eq!(to_markdown(html, BASE_URL), "The total is *`11`*.");
+    }
+
+    #[test]
+    fn converts_code_blocks() {
+        let html = "<pre><code>3   4\n4   3\n&lt;&gt;\n</code></pre>";
+        assert_eq!(to_markdown(html, BASE_URL), "```\n3   4\n4   3\n<>\n```");
+    }
+
+    #[test]
+    fn converts_lists() {
+        let html = "<ul>\n<li>one <code>1</code></li>\n<li>two</li>\n</ul>";
+        assert_eq!(to_markdown(html, BASE_URL), "- one `1`\n- two");
+    }
+
+    #[test]
+    fn extracts_puzzle_description() {
+        let html = r#"<html><head><script>var x = "<p>";</script></head><body>
+            <main>
+            <article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Part
+            one.</p></article>
+            <p>Your puzzle answer was <code>123</code>.</p>
+            <article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part two.</p></article>
+            <p>Both parts of this puzzle are complete!</p>
+            </main></body></html>"#;
+
+        assert_eq!(
+            puzzle_to_markdown(html, BASE_URL),
+            "## --- Day 1: Test ---\n\nPart one.\n\nYour puzzle answer was `123`.\n\n## --- Part Two ---\n\nPart two.\n"
+        );
+    }
+
+    #[test]
+    fn extracts_submission_response() {
+        let html = r#"<main><article><p>That's not the right answer; your answer is too low. <a href="/2025/day/1">[Return to Day 1]</a></p></article></main>"#;
+        assert_eq!(
+            response_to_markdown(html, BASE_URL),
+            "That's not the right answer; your answer is too low. [[Return to Day 1]](https://adventofcode.com/2025/day/1)"
+        );
+    }
+}
@@ -0,0 +1,241 @@
+/// Helpers that split puzzle inputs into their parts.
+/// All helpers accept `\n` and `\r\n` line endings and ignore trailing newlines.
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputError {
+    SectionCount { expected: usize, found: usize },
+    MissingLine,
+    InvalidNumber { row: usize, value: String },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::SectionCount { expected, found } => write!(
+                f,
+                "expected {expected} blank-line separated section(s), found {found}."
+            ),
+            InputError::MissingLine => write!(f, "expected at least two lines."),
+            InputError::InvalidNumber { row, value } => {
+                write!(f, "invalid number `{value}` in row {}.", row + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+fn strip_line_ending(line: &str) -> &str {
+    line.trim_end_matches(['\n', '\r'])
+}
+
+/// Splits an input into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = strip_line_ending(line);
+        if content.trim().is_empty() {
+            if let Some(start) = start.take() {
+                sections.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = start {
+        sections.push(&input[start..end]);
+    }
+
+    sections
+}
+
+/// Splits an input into exactly `N` sections, e.g. the rules and the updates of a puzzle.
+pub fn exact_sections<const N: usize>(input: &str) -> Result<[&str; N], InputError> {
+    let sections = sections(input);
+    let found = sections.len();
+    sections
+        .try_into()
+        .map_err(|_| InputError::SectionCount { expected: N, found })
+}
+
+/// Splits off the first line of a section, e.g. the `Tile 42:` header above a grid.
+pub fn header_body(section: &str) -> Result<(&str, &str), InputError> {
+    let section = section.trim_end_matches(['\n', '\r']);
+    let (header, body) = section.split_once('\n').ok_or(InputError::MissingLine)?;
+    Ok((strip_line_ending(header), body))
+}
+
+/// Splits off the last line of a section, e.g. the row of operators below columns of numbers.
+pub fn body_footer(section: &str) -> Result<(&str, &str), InputError> {
+    let section = section.trim_end_matches(['\n', '\r']);
+    let (body, footer) = section.rsplit_once('\n').ok_or(InputError::MissingLine)?;
+    Ok((strip_line_ending(body), footer))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A block of columns of a fixed-width layout, delimited by columns that are blank in every row.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColumnBlock<'a> {
+    /// First column of the block.
+    pub start: usize,
+    /// Column after the last column of the block.
+    pub end: usize,
+    /// The part of each row within the block. Rows that end early are shorter.
+    pub rows: Vec<&'a str>,
+}
+
+impl ColumnBlock<'_> {
+    /// The part of another line that lies within the block, e.g. the operator below a column of numbers.
+    pub fn slice<'b>(&self, line: &'b str) -> &'b str {
+        let end = self.end.min(line.len());
+        line.get(self.start.min(end)..end).unwrap_or_default()
+    }
+
+    /// The text of each column, read top to bottom, with missing cells as spaces.
+    pub fn columns(&self) -> Vec<String> {
+        (0..self.end - self.start)
+            .map(|column| {
+                self.rows
+                    .iter()
+                    .map(|row| row.as_bytes().get(column).map_or(' ', |b| char::from(*b)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The number in each row, regardless of its alignment.
+    pub fn row_numbers<T: FromStr>(&self) -> Result<Vec<T>, InputError> {
+        parse_numbers(self.rows.iter().map(|row| (*row).to_string()))
+    }
+
+    /// The number in each column, read top to bottom, e.g. for right-aligned vertical numbers.
+    pub fn column_numbers<T: FromStr>(&self) -> Result<Vec<T>, InputError> {
+        parse_numbers(self.columns().into_iter())
+    }
+}
+
+fn parse_numbers<T: FromStr>(values: impl Iterator<Item = String>) -> Result<Vec<T>, InputError> {
+    values
+        .enumerate()
+        .map(|(row, value)| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| InputError::InvalidNumber { row, value })
+        })
+        .collect()
+}
+
+/// Splits a fixed-width layout into blocks of columns separated by blank columns.
+/// Expects ASCII input, as columns are counted in bytes.
+pub fn column_blocks(input: &str) -> Vec<ColumnBlock<'_>> {
+    let rows: Vec<&str> = input.lines().collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    let is_blank = |column: usize| {
+        rows.iter()
+            .all(|row| row.as_bytes().get(column).is_none_or(|b| *b == b' '))
+    };
+
+    let mut blocks = vec![];
+    let mut start = None;
+
+    for column in 0..=width {
+        match (start, column == width || is_blank(column)) {
+            (None, false) => start = Some(column),
+            (Some(block_start), true) => {
+                start = None;
+                blocks.push(ColumnBlock {
+                    start: block_start,
+                    end: column,
+                    rows: rows
+                        .iter()
+                        .map(|row| {
+                            let end = column.min(row.len());
+                            &row[block_start.min(end)..end]
+                        })
+                        .collect(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputError, body_footer, column_blocks, exact_sections, header_body, sections};
+
+    #[test]
+    fn splits_sections() {
+        assert_eq!(sections("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(sections("a\r\nb\r\n\r\n\r\nc"), vec!["a\r\nb", "c"]);
+        assert_eq!(sections("\n\na\n  \nb\n\n"), vec!["a", "b"]);
+        assert!(sections("").is_empty());
+    }
+
+    #[test]
+    fn splits_exact_sections() {
+        assert_eq!(exact_sections::<2>("3-5\n\n1\n5\n"), Ok(["3-5", "1\n5"]));
+        assert_eq!(
+            exact_sections::<2>("3-5\n1\n"),
+            Err(InputError::SectionCount {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn splits_headers_and_footers() {
+        assert_eq!(
+            header_body("Tile 1:\r\n#.\r\n.#\r\n"),
+            Ok(("Tile 1:", "#.\r\n.#"))
+        );
+        assert_eq!(body_footer("1 2\n3 4\n*  +\n"), Ok(("1 2\n3 4", "*  +")));
+        assert_eq!(body_footer("1 2"), Err(InputError::MissingLine));
+    }
+
+    #[test]
+    fn splits_column_blocks() {
+        let (numbers, operators) =
+            body_footer("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n")
+                .unwrap();
+        let blocks = column_blocks(numbers);
+
+        assert_eq!(blocks.len(), 4);
+        assert_eq!((blocks[1].start, blocks[1].end), (4, 7));
+        assert_eq!(blocks[1].rows, vec!["328", "64 ", "98 "]);
+        assert_eq!(blocks[1].slice(operators).trim(), "+");
+        assert_eq!(blocks[3].rows, vec!["64 ", "23 ", "314"]);
+
+        assert_eq!(blocks[0].row_numbers::<u64>(), Ok(vec![123, 45, 6]));
+        assert_eq!(blocks[0].column_numbers::<u64>(), Ok(vec![1, 24, 356]));
+        assert_eq!(blocks[3].column_numbers::<u64>(), Ok(vec![623, 431, 4]));
+    }
+
+    #[test]
+    fn reports_invalid_numbers() {
+        let blocks = column_blocks("12\nab\n");
+        assert_eq!(
+            blocks[0].row_numbers::<u64>(),
+            Err(InputError::InvalidNumber {
+                row: 1,
+                value: "ab".into()
+            })
+        );
+    }
+}
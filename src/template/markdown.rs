@@ -0,0 +1,352 @@
+/// Module that renders puzzle descriptions for the terminal.
+/// Supports the Markdown written by `cargo download`: headings, paragraphs, lists, quotes,
+/// code blocks, emphasis, inline code and links.
+use std::{
+    env,
+    io::{IsTerminal, Write, stdout},
+    process::{Command, Stdio},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_CYAN: &str = "\x1b[36m";
+
+const MIN_WIDTH: usize = 40;
+const MAX_WIDTH: usize = 100;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    emphasis: bool,
+    code: bool,
+    link: bool,
+    dim: bool,
+}
+
+impl Style {
+    fn apply(self, text: &str) -> String {
+        if self == Style::default() {
+            return text.into();
+        }
+
+        let mut styled = String::new();
+        if self.emphasis {
+            styled.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            styled.push_str(ANSI_CYAN);
+        }
+        if self.link {
+            styled.push_str(ANSI_UNDERLINE);
+        }
+        if self.dim {
+            styled.push_str(ANSI_DIM);
+        }
+        styled.push_str(text);
+        styled.push_str(ANSI_RESET);
+        styled
+    }
+}
+
+/// A run of text with a single style.
+type Span = (String, Style);
+
+/// Splits a line of Markdown into styled spans.
+fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans: Vec<Span> = vec![];
+    let mut plain = String::new();
+    let mut rest = text;
+
+    let flush = |plain: &mut String, spans: &mut Vec<Span>| {
+        if !plain.is_empty() {
+            spans.push((std::mem::take(plain), Style::default()));
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let delimited = |open: &str, close: &str| -> Option<(&str, usize)> {
+            let inner = rest.strip_prefix(open)?;
+            // NOTE: a delimiter followed by whitespace is literal, e.g. `2 * 3`.
+            if inner.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let end = inner.find(close).filter(|end| *end > 0)?;
+            Some((&inner[..end], open.len() + end + close.len()))
+        };
+
+        let emphasised_code = Style {
+            emphasis: true,
+            code: true,
+            ..Style::default()
+        };
+
+        let parsed: Option<(Vec<Span>, usize)> = if let Some((inner, len)) = delimited("*`", "`*") {
+            Some((vec![(inner.into(), emphasised_code)], len))
+        } else if let Some((inner, len)) = delimited("`*", "*`") {
+            Some((vec![(inner.into(), emphasised_code)], len))
+        } else if let Some((inner, len)) = delimited("`", "`") {
+            let code = Style {
+                code: true,
+                ..Style::default()
+            };
+            Some((vec![(inner.into(), code)], len))
+        } else if let Some((inner, len)) = delimited("*", "*") {
+            let emphasis = Style {
+                emphasis: true,
+                ..Style::default()
+            };
+            Some((vec![(inner.into(), emphasis)], len))
+        } else if let Some((label, label_len)) = delimited("[", "](")
+            && let Some(url_len) = rest[label_len..].find(')')
+        {
+            let url = &rest[label_len..label_len + url_len];
+            let link = Style {
+                link: true,
+                ..Style::default()
+            };
+            let dim = Style {
+                dim: true,
+                ..Style::default()
+            };
+            Some((
+                vec![(label.into(), link), (format!(" ({url})"), dim)],
+                label_len + url_len + 1,
+            ))
+        } else {
+            None
+        };
+
+        match parsed {
+            Some((parsed, len)) => {
+                flush(&mut plain, &mut spans);
+                spans.extend(parsed);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    flush(&mut plain, &mut spans);
+    spans
+}
+
+/// Wraps styled spans to `width` columns. Every line after the first starts with `indent`.
+fn wrap(spans: &[Span], width: usize, first_prefix: &str, indent: &str) -> Vec<String> {
+    // words are runs of non-whitespace, which can consist of several styled pieces.
+    let mut words: Vec<Vec<(&str, Style)>> = vec![];
+    let mut in_word = false;
+
+    for (text, style) in spans {
+        let mut rest = text.as_str();
+        while !rest.is_empty() {
+            if rest.starts_with(char::is_whitespace) {
+                in_word = false;
+                rest = rest.trim_start();
+                continue;
+            }
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            if !in_word {
+                words.push(vec![]);
+            }
+            words.last_mut().unwrap().push((&rest[..end], *style));
+            in_word = end == rest.len();
+            rest = &rest[end..];
+        }
+    }
+
+    let mut lines = vec![];
+    let mut line = first_prefix.to_string();
+    let mut line_width = first_prefix.chars().count();
+    let mut line_empty = true;
+
+    for word in words {
+        let word_width: usize = word.iter().map(|(text, _)| text.chars().count()).sum();
+
+        if !line_empty && line_width + 1 + word_width > width {
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            line_width = indent.chars().count();
+            line_empty = true;
+        }
+
+        if !line_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        for (text, style) in word {
+            line.push_str(&style.apply(text));
+        }
+        line_width += word_width;
+        line_empty = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+fn list_marker(line: &str) -> Option<&str> {
+    if line.starts_with("- ") || line.starts_with("* ") {
+        return Some(&line[..2]);
+    }
+    let digits = line.find(|c: char| !c.is_ascii_digit())?;
+    (digits > 0 && line[digits..].starts_with(". ")).then(|| &line[..digits + 2])
+}
+
+/// Renders a Markdown document for a terminal of the given width.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out: Vec<String> = vec![];
+    let mut lines = markdown.lines();
+
+    let code = Style {
+        code: true,
+        ..Style::default()
+    };
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+
+        if line.trim().is_empty() {
+            if out.last().is_some_and(|l| !l.is_empty()) {
+                out.push(String::new());
+            }
+        } else if let Some(fence) = ["```", "~~~"].into_iter().find(|f| trimmed.starts_with(f)) {
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with(fence) {
+                    break;
+                }
+                out.push(format!("    {}", code.apply(line)));
+            }
+        } else if let Some(code_line) = line.strip_prefix("    ") {
+            out.push(format!("    {}", code.apply(code_line)));
+        } else if trimmed.starts_with('#') {
+            let heading = trimmed.trim_start_matches('#').trim();
+            out.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            out.extend(wrap(&parse_inline(quote.trim()), width, "│ ", "│ "));
+        } else if let Some(marker) = list_marker(trimmed) {
+            let indent = " ".repeat(marker.len() + 2);
+            let prefix = format!("  {marker}");
+            out.extend(wrap(
+                &parse_inline(&trimmed[marker.len()..]),
+                width,
+                &prefix,
+                &indent,
+            ));
+        } else {
+            // NOTE: single line breaks are kept, they are `<br>`s or the wrapping of the source.
+            out.extend(wrap(&parse_inline(line.trim()), width, "", ""));
+        }
+    }
+
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+
+    out.join("\n") + "\n"
+}
+
+/// Width to wrap text at, taken from `COLUMNS` if set.
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(80_usize)
+        .clamp(MIN_WIDTH, MAX_WIDTH)
+}
+
+/// Prints text through `$PAGER` (or `less`) when stdout is a terminal.
+/// `less` only pages if the text does not fit on one screen.
+pub fn page(text: &str) {
+    if !stdout().is_terminal() {
+        print!("{text}");
+        return;
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".into());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        print!("{text}");
+        return;
+    };
+
+    let mut command = Command::new(program);
+    command.args(parts).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+
+    match command.spawn() {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        Err(_) => print!("{text}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ANSI_CYAN, ANSI_DIM, ANSI_UNDERLINE, render};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn renders_headings() {
+        assert_eq!(
+            render("## --- Day 1: Test ---", 80),
+            format!("{ANSI_BOLD}--- Day 1: Test ---{ANSI_RESET}\n")
+        );
+    }
+
+    #[test]
+    fn wraps_paragraphs() {
+        assert_eq!(
+            render("one two three four five six seven eight nine ten", 40),
+            "one two three four five six seven eight\nnine ten\n"
+        );
+    }
+
+    #[test]
+    fn renders_emphasis_and_code() {
+        assert_eq!(
+            render("The answer is *`42`*, not `7`.", 80),
+            format!(
+                "The answer is {ANSI_BOLD}{ANSI_CYAN}42{ANSI_RESET}, not {ANSI_CYAN}7{ANSI_RESET}.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn renders_links() {
+        assert_eq!(
+            render("Go [here](https://example.com/a).", 80),
+            format!(
+                "Go {ANSI_UNDERLINE}here{ANSI_RESET} {ANSI_DIM}(https://example.com/a){ANSI_RESET}.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_code_blocks() {
+        let code = "a very long line of code that should not be wrapped at all";
+        assert_eq!(
+            render(&format!("```\n{code}\n```\n\nAfter."), 40),
+            format!("    {ANSI_CYAN}{code}{ANSI_RESET}\n\nAfter.\n")
+        );
+    }
+
+    #[test]
+    fn indents_list_items() {
+        assert_eq!(
+            render("- first item that wraps around the width", 40),
+            "  - first item that wraps around the\n    width\n"
+        );
+    }
+}
@@ -1,9 +1,19 @@
-use std::{env, fs};
+use std::fs;
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod aoc_web;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod html;
+pub mod input;
+pub mod markdown;
+pub mod rate_limit;
 pub mod runner;
 
+pub use config::config;
 pub use day::*;
 
 mod day;
@@ -15,11 +25,18 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the event year configured in `aoc.toml` or via the `AOC_YEAR` environment variable.
+#[must_use]
+pub fn get_year() -> Option<u16> {
+    config().year
+}
+
 /// Helper function that reads a text file to a string.
+/// `folder` is a data folder name like `"examples"`, resolved through the `[paths]` configuration.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let config = config();
+    let filepath = config.data_file(config.folder(folder), &format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -27,11 +44,8 @@ pub fn read_file(folder: &str, day: Day) -> String {
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let config = config();
+    let filepath = config.data_file(config.folder(folder), &format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
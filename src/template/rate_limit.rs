@@ -0,0 +1,163 @@
+/// Module that keeps the template from hammering adventofcode.com.
+/// Requests are spaced out across processes, and submission cooldowns are parsed from responses.
+use std::{
+    fs,
+    sync::LazyLock,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET, config};
+
+/// e.g. "You have 4m 12s left to wait." after submitting during a cooldown.
+static LEFT_TO_WAIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"You have (?:(\d+)m ?)?(?:(\d+)s )?left to wait").unwrap());
+
+/// e.g. "please wait one minute before trying again." after a wrong answer.
+static WAIT_BEFORE_RETRY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)wait (\w+) (minute|second)s? before trying again").unwrap());
+
+/// Returns the time until the next submission is possible, if the response mentions one.
+pub fn parse_cooldown(response: &str) -> Option<Duration> {
+    if let Some(captures) = LEFT_TO_WAIT.captures(response) {
+        let minutes: u64 = captures.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+        let seconds: u64 = captures.get(2).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+
+    let captures = WAIT_BEFORE_RETRY.captures(response)?;
+    let amount = parse_number(&captures[1])?;
+    let unit = if captures[2].eq_ignore_ascii_case("minute") {
+        60
+    } else {
+        1
+    };
+    Some(Duration::from_secs(amount * unit))
+}
+
+fn parse_number(s: &str) -> Option<u64> {
+    const WORDS: [&str; 11] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+
+    s.parse().ok().or_else(|| {
+        WORDS
+            .iter()
+            .position(|word| word.eq_ignore_ascii_case(s))
+            .map(|n| n as u64)
+    })
+}
+
+/// Formats a wait time like the server does, e.g. `4m 12s`.
+pub fn format_wait(duration: Duration) -> String {
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis())
+}
+
+/// Blocks until `interval` has passed since the last request to adventofcode.com,
+/// then records the current request. The time of the last request is shared between processes
+/// through a file in the data directory, so loops over `cargo download` are throttled too.
+pub fn throttle(interval: Duration) {
+    if interval.is_zero() {
+        return;
+    }
+
+    let path = config().paths.data.join(".last_request");
+
+    let last_request: Option<u128> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| s.trim().parse().ok());
+
+    if let Some(last_request) = last_request {
+        let elapsed = now_millis().saturating_sub(last_request);
+        if let Some(remaining) = interval.as_millis().checked_sub(elapsed)
+            && remaining > 0
+        {
+            #[allow(clippy::cast_possible_truncation)]
+            let remaining = Duration::from_millis(remaining as u64);
+            println!(
+                "{ANSI_ITALIC}Waiting {} before the next request...{ANSI_RESET}",
+                format_wait(remaining)
+            );
+            thread::sleep(remaining);
+        }
+    }
+
+    let _ = fs::write(&path, now_millis().to_string());
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_wait, parse_cooldown};
+
+    #[test]
+    fn parses_remaining_wait() {
+        assert_eq!(
+            parse_cooldown(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 12s left to wait."
+            ),
+            Some(Duration::from_secs(252))
+        );
+        assert_eq!(
+            parse_cooldown("You have 30s left to wait."),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_cooldown("You have 2m left to wait."),
+            Some(Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn parses_retry_wait() {
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer. If you're stuck, make sure you're using the full input data; please wait one minute before trying again."
+            ),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_cooldown(
+                "Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."
+            ),
+            Some(Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn ignores_responses_without_wait() {
+        assert_eq!(parse_cooldown("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn formats_waits() {
+        assert_eq!(format_wait(Duration::from_secs(252)), "4m 12s");
+        assert_eq!(format_wait(Duration::from_secs(60)), "1m");
+        assert_eq!(format_wait(Duration::from_millis(29_500)), "30s");
+    }
+}
@@ -2,10 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
 use crate::template::timings::Timings;
-
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::{Day, config, event_days};
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -27,11 +25,11 @@ pub struct TablePosition {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./{}", config().bin_path(day).display())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,11 +50,11 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn construct_table(prefix: &str, marker: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
@@ -76,32 +74,47 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    marker: &str,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    let table = construct_table("##", marker, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+pub fn update(mut timings: Timings) -> Result<(), Error> {
+    let path = &config().readme.path;
+    // drop stale timings of days that are not part of the configured event.
+    let days = event_days();
+    timings.data.retain(|timing| days.contains(timing.day));
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        &config().readme.benchmarks_marker,
+        timings,
+        total_millis,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
+    use super::update_content;
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
+    const MARKER: &str = "<!--- benchmarking table --->";
+
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
@@ -131,28 +144,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, MARKER, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, MARKER, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, MARKER, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, MARKER, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, MARKER, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +173,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, MARKER, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
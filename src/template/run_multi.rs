@@ -1,6 +1,6 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, config};
 
 use super::{
     all_days,
@@ -61,7 +61,7 @@ impl From<std::io::Error> for Error {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    config().bin_path(day).display().to_string()
 }
 
 /// All solutions live in isolated binaries.
@@ -2,20 +2,30 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::{AnswerBook, Verdict};
+use crate::template::aoc_client::{AocClientError, client};
+use crate::template::rate_limit::{format_wait, parse_cooldown};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, config};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(func, input, |result| {
+        print_result(result, &part_str, "", "")
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let check = check_accepted(result.as_ref(), day, part);
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&duration, samples),
+        &check,
+    );
 
     if let Some(result) = result {
         submit_result(result, day, part);
@@ -24,12 +34,13 @@ pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, da
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. the configured target duration or the minimum number of samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
+    let watchdog = start_watchdog();
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -38,6 +49,7 @@ fn run_timed<I: Copy, T>(
         func(input)
     };
     let base_time = timer.elapsed();
+    drop(watchdog);
 
     hook(&result);
 
@@ -50,14 +62,32 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
+/// Exits the process if the solution does not finish within the configured timeout.
+/// The watchdog is cancelled when the returned sender is dropped.
+fn start_watchdog() -> Option<mpsc::Sender<()>> {
+    let timeout = config().timeouts.solution?;
+    let (tx, rx) = mpsc::channel::<()>();
+
+    thread::spawn(move || {
+        if let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(timeout) {
+            println!();
+            eprintln!("Solution did not finish within {timeout:?}.");
+            process::exit(1);
+        }
+    });
+
+    Some(tx)
+}
+
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bounds = &config().benchmark;
+    let bench_iterations = (bounds.target.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(bounds.min_iterations, bounds.max_iterations);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -90,13 +120,27 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Compares a result with the accepted answer of the part, if the answer book has one.
+/// Returns a marker to append to the printed result: ` ✓` on a match, ` ✗ ...` on a regression.
+fn check_accepted<T: Display>(result: Option<&T>, day: Day, part: u8) -> String {
+    let Ok(book) = AnswerBook::read_with_puzzle(day) else {
+        return String::new();
+    };
+
+    match (book.accepted(part), result) {
+        (None, _) => String::new(),
+        (Some(accepted), Some(result)) if result.to_string() == accepted => " ✓".into(),
+        (Some(accepted), _) => format!(" ✗ (accepted answer: {accepted})"),
+    }
+}
+
+fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str, check: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: ▼ {duration_str}{check}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -105,7 +149,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}{check}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -119,7 +163,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖{check}             ");
             }
         }
     }
@@ -127,12 +171,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the configured client backend is available.
+///  3. the answer book does not rule out the answer.
+///  4. no submission cooldown is active, unless `--wait` was passed.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,13 +201,56 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
+    let answer = result.to_string();
+
+    let mut book = match AnswerBook::read_with_puzzle(day) {
+        Ok(book) => book,
+        Err(e) => {
+            eprintln!("failed to read answer book: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(rejection) = book.check(part, &answer) {
+        eprintln!("Not submitting `{answer}`: {rejection}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    if let Some(cooldown) = book.remaining_cooldown() {
+        if !args.contains(&"--wait".into()) {
+            eprintln!(
+                "Submissions for day {day} are locked for another {}. Pass `--wait` to wait for the cooldown.",
+                format_wait(cooldown)
+            );
+            process::exit(1);
+        }
+
+        println!(
+            "{ANSI_ITALIC}Waiting {} for the submission cooldown...{ANSI_RESET}",
+            format_wait(cooldown)
+        );
+        thread::sleep(cooldown);
+    }
+
+    println!("Submitting result...");
+    let response = client().submit(day, part, &answer);
+
+    match &response {
+        Ok(message) => {
+            println!("{}", message.trim_end());
+            book.record(part, &answer, Verdict::from_response(message));
+            if let Some(cooldown) = parse_cooldown(message) {
+                book.lock_for(cooldown);
+            }
+            if let Err(e) = book.store_file() {
+                eprintln!("failed to store answer book: {e}");
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            process::exit(1);
+        }
+    }
+
+    Some(response)
 }
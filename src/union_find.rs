@@ -0,0 +1,257 @@
+//! Disjoint sets of arbitrary keys, for puzzles that join things into clusters.
+//!
+//! [`UnionFind`] uses union by rank and path compression, so a sequence of unions and lookups
+//! runs in near-linear time.
+//!
+//! ```
+//! use advent_of_code::union_find::UnionFind;
+//!
+//! let mut circuits: UnionFind<&str> = ["a", "b", "c", "d"].into_iter().collect();
+//! circuits.union("a", "b");
+//! circuits.union("b", "c");
+//! assert!(circuits.connected(&"a", &"c"));
+//! assert_eq!(circuits.component_count(), 2);
+//! assert_eq!(circuits.k_largest(2), vec![3, 1]);
+//! ```
+use std::{collections::HashMap, hash::Hash};
+
+use itertools::Itertools;
+
+/// A partition of keys into disjoint sets.
+#[derive(Clone, Debug, Default)]
+pub struct UnionFind<K: Hash + Eq + Clone> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    /// Size of each set, only valid for the root of the set.
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl<K: Hash + Eq + Clone> UnionFind<K> {
+    pub fn new() -> Self {
+        Self {
+            indices: HashMap::new(),
+            keys: vec![],
+            parents: vec![],
+            ranks: vec![],
+            sizes: vec![],
+            components: 0,
+        }
+    }
+
+    /// Adds a key as a set of its own. Returns `false` if the key was already present.
+    pub fn insert(&mut self, key: K) -> bool {
+        if self.indices.contains_key(&key) {
+            return false;
+        }
+        self.index_or_insert(key);
+        true
+    }
+
+    fn index_or_insert(&mut self, key: K) -> usize {
+        if let Some(index) = self.indices.get(&key) {
+            return *index;
+        }
+        let index = self.keys.len();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+        self.parents.push(index);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.components += 1;
+        index
+    }
+
+    fn root(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // path compression: point everything on the way directly at the root.
+        let mut current = index;
+        while self.parents[current] != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+
+        root
+    }
+
+    /// The representative key of the set containing `key`, if the key is present.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = *self.indices.get(key)?;
+        let root = self.root(index);
+        Some(&self.keys[root])
+    }
+
+    /// Joins the sets containing `a` and `b`, adding keys that are not present yet.
+    /// Returns `false` if they were already in the same set.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.index_or_insert(a);
+        let b = self.index_or_insert(b);
+        let (a, b) = (self.root(a), self.root(b));
+        if a == b {
+            return false;
+        }
+
+        let (parent, child) = if self.ranks[a] >= self.ranks[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[child] = parent;
+        self.sizes[parent] += self.sizes[child];
+        if self.ranks[parent] == self.ranks[child] {
+            self.ranks[parent] += 1;
+        }
+        self.components -= 1;
+        true
+    }
+
+    /// Whether both keys are present and in the same set.
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(a), Some(b)) => {
+                let (a, b) = (*a, *b);
+                self.root(a) == self.root(b)
+            }
+            _ => false,
+        }
+    }
+
+    /// The size of the set containing `key`, or 0 if the key is not present.
+    pub fn size_of(&mut self, key: &K) -> usize {
+        match self.indices.get(key) {
+            Some(index) => {
+                let root = self.root(*index);
+                self.sizes[root]
+            }
+            None => 0,
+        }
+    }
+
+    /// The number of keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The number of disjoint sets.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The size of every set, in no particular order.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|(index, parent)| index == *parent)
+            .map(|(index, _)| self.sizes[index])
+    }
+
+    /// The sizes of the `k` largest sets, largest first.
+    pub fn k_largest(&self, k: usize) -> Vec<usize> {
+        self.component_sizes().k_largest(k).collect()
+    }
+
+    /// The keys of every set, ordered by their first inserted key.
+    pub fn components(&mut self) -> Vec<Vec<K>> {
+        let mut positions: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<K>> = vec![];
+
+        for index in 0..self.keys.len() {
+            let root = self.root(index);
+            let position = *positions.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[position].push(self.keys[index].clone());
+        }
+
+        components
+    }
+}
+
+impl<K: Hash + Eq + Clone> FromIterator<K> for UnionFind<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut union_find = Self::new();
+        union_find.extend(iter);
+        union_find
+    }
+}
+
+impl<K: Hash + Eq + Clone> Extend<K> for UnionFind<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::UnionFind;
+
+    #[test]
+    fn joins_sets() {
+        let mut sets: UnionFind<u32> = (0..6).collect();
+        assert_eq!(sets.component_count(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.connected(&0, &3));
+        assert!(!sets.connected(&0, &4));
+        let root = sets.find(&2).copied();
+        assert_eq!(sets.find(&1).copied(), root);
+        assert_eq!(sets.component_count(), 3);
+        assert_eq!(sets.size_of(&3), 4);
+        assert_eq!(sets.size_of(&5), 1);
+        assert_eq!(sets.k_largest(2), vec![4, 1]);
+    }
+
+    #[test]
+    fn inserts_missing_keys() {
+        let mut sets: UnionFind<(i32, i32, i32)> = UnionFind::new();
+        assert!(sets.union((1, 2, 3), (4, 5, 6)));
+        assert!(!sets.insert((1, 2, 3)));
+        assert!(sets.insert((7, 8, 9)));
+
+        assert_eq!(sets.len(), 3);
+        assert_eq!(sets.component_count(), 2);
+        assert_eq!(sets.size_of(&(0, 0, 0)), 0);
+        assert_eq!(sets.find(&(0, 0, 0)), None);
+        assert!(!sets.connected(&(1, 2, 3), &(0, 0, 0)));
+    }
+
+    #[test]
+    fn lists_components() {
+        let mut sets: UnionFind<char> = "abcde".chars().collect();
+        sets.union('e', 'a');
+        sets.union('b', 'd');
+        assert_eq!(
+            sets.components(),
+            vec![vec!['a', 'e'], vec!['b', 'd'], vec!['c']]
+        );
+    }
+
+    #[test]
+    fn handles_long_chains() {
+        let mut sets: UnionFind<usize> = UnionFind::new();
+        for i in 1..100_000 {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(sets.component_count(), 1);
+        assert_eq!(sets.size_of(&0), 100_000);
+    }
+}